@@ -0,0 +1,62 @@
+//! Benchmarks for the operations whose cost matters for performance work
+//! on the crate: the constant-time comparison (libsodium-backed when that
+//! feature is on, volatile loop otherwise), the volatile wipe, and the
+//! clone/reallocation paths.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use secstr::SecStr;
+
+const SIZES: [usize; 4] = [32, 256, 4096, 65536];
+
+fn bench_cmp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cmp");
+    for size in SIZES {
+        let a = SecStr::from(vec![0x5au8; size]);
+        let b = SecStr::from(vec![0x5au8; size]);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |bench, _| {
+            bench.iter(|| a == b)
+        });
+    }
+    group.finish();
+}
+
+fn bench_zero_out(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zero_out");
+    for size in SIZES {
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |bench, &size| {
+            bench.iter_batched(
+                || SecStr::from(vec![0x5au8; size]),
+                |mut sec| sec.zero_out(),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone");
+    for size in SIZES {
+        let sec = SecStr::from(vec![0x5au8; size]);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |bench, _| {
+            bench.iter(|| sec.clone())
+        });
+    }
+    group.finish();
+}
+
+fn bench_reserve_regrow(c: &mut Criterion) {
+    c.bench_function("reserve_regrow", |bench| {
+        bench.iter_batched(
+            || SecStr::from(vec![0x5au8; 1024]),
+            |mut sec| sec.reserve_exact(1024),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_cmp, bench_zero_out, bench_clone, bench_reserve_regrow);
+criterion_main!(benches);
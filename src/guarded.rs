@@ -0,0 +1,166 @@
+//! Guard-page-protected secret storage, behind the `guard-pages` feature
+//! (unix only): the value lives on its own locked pages with an
+//! inaccessible (`PROT_NONE`) page on either side, so a buffer over- or
+//! under-read faults instead of leaking adjacent memory into the secret or
+//! vice versa. Costs at least three pages of address space per secret.
+
+use std::fmt;
+use std::mem::size_of;
+use std::ptr;
+
+use crate::{mem, memlock, NoPaddingBytes};
+
+/// A secured box whose allocation is surrounded by inaccessible guard
+/// pages, with the usual locking, zeroing in `Drop`, constant time
+/// comparison and `***SECRET***` formatting. For high-value keys where the
+/// multi-page overhead per secret is acceptable.
+pub struct SecGuardedBox<T>
+where
+    T: Sized + Copy,
+{
+    /// points at the value, at the start of the readable region
+    data: *mut T,
+    /// base of the whole mapping, including both guards
+    base: *mut libc::c_void,
+    map_len: usize,
+}
+
+impl<T> SecGuardedBox<T>
+where
+    T: Sized + Copy,
+{
+    /// Move `value` behind guard pages. The stack copy this function
+    /// received is wiped before returning; copies the compiler made while
+    /// the value was being built are out of this crate's hands, as with
+    /// `SecStr::from`.
+    ///
+    /// Panics if the mapping or either guard page cannot be set up —
+    /// a box without working guards would be this type's whole
+    /// purpose, silently missing.
+    pub fn new(mut value: T) -> Self {
+        assert!(size_of::<T>() > 0, "guard pages around a zero-sized type are pointless");
+        let page = memlock::page_size();
+        let data_len = size_of::<T>().div_ceil(page) * page;
+        let map_len = data_len + 2 * page;
+        unsafe {
+            let base = libc::mmap(
+                ptr::null_mut(),
+                map_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANON,
+                -1,
+                0,
+            );
+            assert!(base != libc::MAP_FAILED, "mmap failed for a guarded allocation");
+            let base_bytes = base as *mut u8;
+            assert!(
+                libc::mprotect(base, page, libc::PROT_NONE) == 0,
+                "mprotect failed for the leading guard page"
+            );
+            assert!(
+                libc::mprotect(base_bytes.add(page + data_len) as *mut libc::c_void, page, libc::PROT_NONE) == 0,
+                "mprotect failed for the trailing guard page"
+            );
+            let data = base_bytes.add(page) as *mut T;
+            memlock::mlock(data, 1);
+            // sound here, unlike on heap secrets: the mapping is page-
+            // granular and exclusively ours, so no neighbour gets wiped
+            memlock::wipe_on_fork(data, 1);
+            ptr::write(data, value);
+            // SAFETY: `value` is this function's own copy (`T: Copy`, so
+            // the write above did not invalidate it) and is not read again
+            mem::zero(&mut value as *mut T, 1);
+            SecGuardedBox { data, base, map_len }
+        }
+    }
+
+    /// Borrow the contents of the box.
+    #[must_use]
+    pub fn unsecure(&self) -> &T {
+        // SAFETY: `data` points at an initialized `T` inside the readable
+        // region of a mapping we own.
+        unsafe { &*self.data }
+    }
+
+    /// Mutably borrow the contents of the box.
+    #[must_use]
+    pub fn unsecure_mut(&mut self) -> &mut T {
+        // SAFETY: see `unsecure`.
+        unsafe { &mut *self.data }
+    }
+}
+
+// SAFETY: the raw pointers refer to a mapping exclusively owned by this
+// value, so it is exactly as thread-safe as the `T` it stores.
+unsafe impl<T> Send for SecGuardedBox<T> where T: Sized + Copy + Send {}
+unsafe impl<T> Sync for SecGuardedBox<T> where T: Sized + Copy + Sync {}
+
+// Comparisons
+impl<T> PartialEq for SecGuardedBox<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn eq(&self, other: &SecGuardedBox<T>) -> bool {
+        // SAFETY: both pointers refer to one initialized `T` and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        unsafe { mem::cmp(self.data, other.data, 1) }
+    }
+}
+
+impl<T> Eq for SecGuardedBox<T> where T: Sized + Copy + NoPaddingBytes {}
+
+// Make sure sensitive information is not logged accidentally
+impl<T> fmt::Debug for SecGuardedBox<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl<T> fmt::Display for SecGuardedBox<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+// Delete sensitive information from memory
+impl<T> Drop for SecGuardedBox<T>
+where
+    T: Sized + Copy,
+{
+    fn drop(&mut self) {
+        unsafe {
+            mem::zero(self.data, 1);
+            memlock::munlock(self.data, 1);
+            libc::munmap(self.base, self.map_len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guarded_basic() {
+        let mut my_sec = SecGuardedBox::new([1u8, 2, 3]);
+        assert_eq!(my_sec.unsecure(), &[1u8, 2, 3]);
+        my_sec.unsecure_mut()[0] = 9;
+        assert_eq!(my_sec.unsecure(), &[9u8, 2, 3]);
+        assert_eq!(my_sec, SecGuardedBox::new([9u8, 2, 3]));
+        assert_ne!(my_sec, SecGuardedBox::new([1u8, 2, 3]));
+        assert_eq!(format!("{:?}", my_sec), "***SECRET***");
+    }
+
+    #[test]
+    fn test_guarded_large() {
+        // larger than a page, to exercise the rounded data region
+        let my_sec = SecGuardedBox::new([7u8; 8192]);
+        assert!(my_sec.unsecure().iter().all(|b| *b == 7));
+    }
+}
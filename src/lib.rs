@@ -0,0 +1,4174 @@
+//! A data type suitable for storing sensitive information such as passwords and private keys in memory, that implements:
+//!
+//! - Automatic zeroing in `Drop`
+//! - Constant time comparison in `PartialEq` (does not short circuit on the first different character; but terminates instantly if strings have different length)
+//! - Outputting `***SECRET***` to prevent leaking secrets into logs with `fmt::Debug` and `fmt::Display`
+//! - Automatic `mlock` to protect against leaking into swap (any unix)
+//! - Automatic `madvise(MADV_NOCORE/MADV_DONTDUMP)` to protect against leaking into core dumps (FreeBSD, DragonFlyBSD, Linux)
+//!
+//! Be careful with `SecStr::from`: if you have a borrowed string, it will be copied.
+//! Use `SecStr::new` if you have a `Vec<u8>`.
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
+
+#[cfg(all(feature = "backend-sodium", feature = "backend-pure"))]
+compile_error!(
+    "features `backend-sodium` and `backend-pure` are mutually exclusive: \
+     pick one constant-time backend"
+);
+
+use std::borrow::{Borrow, BorrowMut};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::de::{Deserialize, Deserializer, Visitor};
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, Serializer};
+
+#[cfg(all(feature = "guard-pages", unix))]
+mod guarded;
+mod mem;
+mod memlock;
+mod pool;
+#[cfg(feature = "allocator-api")]
+mod sodium_alloc;
+
+#[cfg(all(feature = "guard-pages", unix))]
+pub use guarded::SecGuardedBox;
+pub use memlock::{set_lock_failure_policy, LockFailurePolicy};
+pub use pool::{PooledSec, SecPool};
+#[cfg(feature = "allocator-api")]
+pub use sodium_alloc::SodiumAllocator;
+
+/// Compare two byte slices in constant time, without wrapping either in a
+/// `SecStr`: the runtime depends on the length, but not on the contents.
+/// Uses libsodium's `sodium_memcmp` when the `libsodium-sys` feature is
+/// enabled, a volatile byte-by-byte loop otherwise.
+///
+/// Note: just like `PartialEq` on `SecStr`, this terminates instantly on a
+/// length mismatch, so the length is treated as public.
+#[must_use]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // SAFETY: both slices contain `a.len()` initialized bytes.
+    unsafe { mem::cmp(a.as_ptr(), b.as_ptr(), a.len()) }
+}
+
+/// Compare two byte-ish values in constant time, with the same contract as
+/// Python's `hmac.compare_digest`: accepts anything that views as bytes
+/// (`&[u8]`, `&str`, `String`, `Vec<u8>`, arrays, …), returns `false` on a
+/// length mismatch without reading the contents, and otherwise compares
+/// every byte with no early exit. Neither operand needs to be a `SecStr` —
+/// this is the migration-friendly spelling of [`constant_time_eq`], for
+/// ported code that compares digests it never wrapped.
+///
+/// [`constant_time_eq`]: fn.constant_time_eq.html
+#[must_use]
+pub fn compare_digest<A: AsRef<[u8]>, B: AsRef<[u8]>>(a: A, b: B) -> bool {
+    constant_time_eq(a.as_ref(), b.as_ref())
+}
+
+/// Wipe a whole collection of secrets in one call: `zero_out` every
+/// element, in order. Each `SecVec` would wipe itself on drop anyway; the
+/// point of the bulk helper is wiping *now* (e.g. a session store at
+/// shutdown) without waiting on the container's drop order, and having one
+/// canonical call site instead of a hand-rolled loop that forgets entries.
+pub fn zero_all<T: Sized + Copy>(items: &mut [SecVec<T>]) {
+    for item in items.iter_mut() {
+        item.zero_out();
+    }
+}
+
+/// Verify a MAC/HMAC tag in constant time: compare a stored expected tag
+/// against a freshly `computed` one without requiring the computed side to
+/// be wrapped in a `SecStr` first.
+///
+/// The tag length is assumed to be public (it is fixed by the MAC
+/// algorithm): a length mismatch returns `false` immediately, and the
+/// comparison time depends only on the length, never on the contents.
+#[must_use]
+pub fn verify_mac(expected: &SecStr, computed: &[u8]) -> bool {
+    constant_time_eq(expected.unsecure(), computed)
+}
+
+/// `subtle::Choice`-returning variant of [`verify_mac`], for callers
+/// composing the result into further branchless logic.
+#[cfg(feature = "subtle")]
+#[must_use]
+pub fn verify_mac_choice(expected: &SecStr, computed: &[u8]) -> subtle::Choice {
+    subtle::Choice::from(verify_mac(expected, computed) as u8)
+}
+
+/// Marker trait for types whose in-memory representation consists entirely
+/// of initialized bytes (no padding), making a byte-wise comparison of two
+/// values meaningful.
+///
+/// # Safety
+///
+/// Implementing this trait for a type with padding bytes makes the
+/// byte-wise `PartialEq` read uninitialized memory, which is undefined
+/// behavior.
+///
+/// The array impls propagate the property from the element type rather
+/// than claiming it for any `[T; N]` — an array of a padded struct has
+/// padding too, so this must not compile:
+///
+/// ```compile_fail
+/// #[derive(Clone, Copy)]
+/// struct Padded(u8, u32); // three padding bytes after the `u8`
+/// let a: secstr::SecVec<[Padded; 4]> = secstr::SecVec::new(vec![[Padded(1, 2); 4]]);
+/// let b: secstr::SecVec<[Padded; 4]> = secstr::SecVec::new(vec![[Padded(1, 2); 4]]);
+/// let _ = a == b; // `[Padded; 4]: NoPaddingBytes` is unsatisfied
+/// ```
+pub unsafe trait NoPaddingBytes {}
+
+macro_rules! impl_no_padding_bytes {
+    ($($type:ty),*) => {
+        $(unsafe impl NoPaddingBytes for $type {})*
+    };
+}
+
+impl_no_padding_bytes! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64, char, ()
+}
+
+// SAFETY: an array of a padding-free `T` is itself padding-free — the
+// stride equals `size_of::<T>()`, so there are no gap bytes between
+// elements. The `T: NoPaddingBytes` bound is what makes this sound: a
+// blanket `T: Sized + Copy` would smuggle padded structs in through their
+// arrays.
+unsafe impl<T: NoPaddingBytes, const N: usize> NoPaddingBytes for [T; N] {}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Marker trait for types for which the all-zero byte pattern is a valid
+/// value, making it safe to wipe one in place and keep using it. Sealed:
+/// the crate implements it for the primitives and arrays of them, the same
+/// coverage as [`NoPaddingBytes`], and that set is the contract — a wrong impl
+/// downstream would make [`SecBox::wipe`](struct.SecBox.html#method.wipe)
+/// undefined behavior.
+pub trait ZeroValid: sealed::Sealed {}
+
+macro_rules! impl_zero_valid {
+    ($($type:ty),*) => {
+        $(
+            impl sealed::Sealed for $type {}
+            impl ZeroValid for $type {}
+        )*
+    };
+}
+
+impl_zero_valid! {
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64, char, ()
+}
+
+// An array of zero-valid elements is itself zero-valid: the all-zero
+// pattern just zeroes each element. Every length, like `NoPaddingBytes`.
+impl<T: ZeroValid, const N: usize> sealed::Sealed for [T; N] {}
+impl<T: ZeroValid, const N: usize> ZeroValid for [T; N] {}
+
+/// Error returned by [`SecVec::try_unsecure`]: the secret was explicitly
+/// wiped (`zero_out`) and has not been repopulated since, so there is
+/// nothing meaningful to read.
+///
+/// [`SecVec::try_unsecure`]: struct.SecVec.html#method.try_unsecure
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Wiped;
+
+impl fmt::Display for Wiped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the secret was wiped")
+    }
+}
+
+impl std::error::Error for Wiped {}
+
+/// A data type suitable for storing sensitive information such as passwords and private keys in memory, that implements:
+///
+/// - Automatic zeroing in `Drop`
+/// - Constant time comparison in `PartialEq` (does not short circuit on the first different character; but terminates instantly if strings have different length)
+/// - Outputting `***SECRET***` to prevent leaking secrets into logs with `fmt::Debug` and `fmt::Display`
+/// - Automatic `mlock` to protect against leaking into swap (any unix)
+/// - Automatic `madvise(MADV_NOCORE/MADV_DONTDUMP)` to protect against leaking into core dumps (FreeBSD, DragonFlyBSD, Linux)
+///
+/// Be careful with `SecStr::from`: if you have a borrowed string, it will be copied.
+/// Use `SecStr::new` if you have a `Vec<u8>`.
+///
+/// Thread safety: `SecVec<T>` is `Send` and `Sync` exactly like `Vec<T>` —
+/// all of its own state is plain data, with no interior mutability — so
+/// secrets can be shared across threads behind the usual synchronization.
+/// The `test_send_sync` assertion keeps future internal state honest.
+pub struct SecVec<T>
+where
+    T: Sized + Copy,
+{
+    content: Vec<T>,
+    /// Whether the most recent `mlock` of the current buffer succeeded.
+    locked: bool,
+    /// Whether this instance uses `mlock` at all; `false` only for
+    /// [`new_unlocked`](Self::new_unlocked). Gates every lock *and* unlock,
+    /// so an unlocked instance makes no locking syscalls over its whole
+    /// lifetime.
+    locking: bool,
+    /// Whether the contents were explicitly wiped (`zero_out`) and not
+    /// repopulated since; distinguishes "wiped" from "legitimately empty"
+    /// for `try_unsecure`.
+    wiped: bool,
+    /// Label attached to audit events for this secret; never its contents.
+    #[cfg(feature = "audit")]
+    label: Option<std::borrow::Cow<'static, str>>,
+}
+
+/// Type alias for a `SecVec` of bytes.
+pub type SecStr = SecVec<u8>;
+
+impl<T> SecVec<T>
+where
+    T: Sized + Copy,
+{
+    /// Wrap the given `Vec`. The exact backing store handed in is the one
+    /// that gets locked — the data is not copied (though any copies made
+    /// while the `Vec` was being built are out of this crate's hands).
+    pub fn new(cont: Vec<T>) -> Self {
+        let locked = memlock::mlock(cont.as_ptr(), cont.capacity());
+        SecVec {
+            content: cont,
+            locked,
+            locking: true,
+            wiped: false,
+            #[cfg(feature = "audit")]
+            label: None,
+        }
+    }
+
+    /// Wrap the given `Vec` *without* locking it into physical memory:
+    /// zeroing on drop, constant-time comparison and debug-output masking
+    /// all still apply, but the buffer may reach swap. A deliberate
+    /// tradeoff for high-churn workloads (e.g. millions of short-lived
+    /// per-request nonces), where the `mlock`/`munlock` syscall pair per
+    /// secret dominates cost and thrashes the locked-page accounting —
+    /// no locking syscalls are ever made for this instance, including on
+    /// reallocation and drop. Use [`new`](Self::new) unless profiling
+    /// says otherwise.
+    pub fn new_unlocked(cont: Vec<T>) -> Self {
+        SecVec {
+            content: cont,
+            locked: false,
+            locking: false,
+            wiped: false,
+            #[cfg(feature = "audit")]
+            label: None,
+        }
+    }
+
+    /// Whether the backing buffer is currently locked in physical memory,
+    /// i.e. whether the most recent `mlock` on it (at construction or after
+    /// a reallocation) succeeded. `false` can mean the platform has no
+    /// `mlock`, that the instance was created with
+    /// [`new_unlocked`](Self::new_unlocked), or that a resource limit like
+    /// `RLIMIT_MEMLOCK` was hit — the
+    /// contents still get zeroed on drop either way, they just might have
+    /// been swapped out in the meantime. A zero-length buffer has nothing
+    /// to lock and reports `true` on every platform.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Borrow the contents of the string.
+    #[must_use]
+    pub fn unsecure(&self) -> &[T] {
+        self.audit_event(false);
+        self.borrow()
+    }
+
+    /// Mutably borrow the contents of the string.
+    #[must_use]
+    pub fn unsecure_mut(&mut self) -> &mut [T] {
+        self.audit_event(true);
+        self.borrow_mut()
+    }
+
+    /// Borrow the contents like [`unsecure`](Self::unsecure), but fail with
+    /// [`Wiped`] if the secret was explicitly zeroed (`zero_out`) and not
+    /// repopulated since. A plain `unsecure` on a wiped secret silently
+    /// returns an empty slice, which downstream code can mistake for real
+    /// (all-zero) key material; this turns that use-after-wipe into a loud
+    /// error while still treating a never-wiped empty buffer as fine.
+    ///
+    /// [`Wiped`]: struct.Wiped.html
+    pub fn try_unsecure(&self) -> Result<&[T], Wiped> {
+        if self.wiped {
+            return Err(Wiped);
+        }
+        Ok(self.unsecure())
+    }
+
+    /// Attach a label to this secret's audit events (the events never
+    /// contain the contents, so an identifying label is what makes the
+    /// trail readable). Builder-style, for use at construction:
+    /// `SecStr::from(pw).with_label("db-password")`.
+    #[cfg(feature = "audit")]
+    pub fn with_label(mut self, label: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Emit the exposure audit event. Compiles to nothing without the
+    /// `audit` feature.
+    #[inline]
+    fn audit_event(&self, mutable: bool) {
+        #[cfg(feature = "audit")]
+        tracing::trace!(
+            target: "secstr::audit",
+            label = self.label.as_deref().unwrap_or("<unlabeled>"),
+            mutable,
+            len = self.content.len(),
+            "secret exposed"
+        );
+        #[cfg(not(feature = "audit"))]
+        let _ = mutable;
+    }
+
+    /// Hand the contents to a closure as a read-only slice. Functionally
+    /// the same as `unsecure`, but keeps the exposure window visually
+    /// bounded in the source: the slice cannot escape the closure.
+    pub fn with_unsecure<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&[T]) -> R,
+    {
+        f(self.unsecure())
+    }
+
+    /// Hand the contents to a closure as a mutable slice, like
+    /// `with_unsecure` but for in-place edits.
+    pub fn with_unsecure_mut<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut [T]) -> R,
+    {
+        f(self.unsecure_mut())
+    }
+
+    /// Copy `src` into a freshly locked buffer: the new allocation is
+    /// locked *before* the copy, so the contents never sit in unlocked
+    /// memory. The common body of `clone`, `split_at` and friends.
+    fn from_slice_locked(src: &[T]) -> Self {
+        let mut content = Vec::with_capacity(src.len());
+        let locked = memlock::mlock(content.as_ptr(), content.capacity());
+        content.extend_from_slice(src);
+        SecVec {
+            content,
+            locked,
+            locking: true,
+            wiped: false,
+            #[cfg(feature = "audit")]
+            label: None,
+        }
+    }
+
+    /// Copy out the first element, if any — reads a single element of the
+    /// secret without borrowing the whole slice.
+    pub fn first(&self) -> Option<T> {
+        self.content.first().copied()
+    }
+
+    /// Copy out the last element, if any; see [`first`](Self::first).
+    pub fn last(&self) -> Option<T> {
+        self.content.last().copied()
+    }
+
+    /// Copy the given sub-range into a new secured buffer of its own,
+    /// locked before the copy — for protocol parsing that only needs part
+    /// of a secret at a time (e.g. a key ID prefix), without exposing the
+    /// rest.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds, like slice indexing.
+    pub fn copy_range<R>(&self, range: R) -> SecVec<T>
+    where
+        R: std::slice::SliceIndex<[T], Output = [T]>,
+    {
+        SecVec::from_slice_locked(&self.content[range])
+    }
+
+    /// Split the secret at `mid` into two independent secured owners: the
+    /// first holds elements `..mid`, the second `mid..`. Each half is
+    /// copied straight into its own freshly locked buffer, with no
+    /// intermediate unlocked copy — for carving an HKDF-expanded keystream
+    /// into separate keys. The split index is treated as public; `self` is
+    /// left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`, like `slice::split_at`.
+    pub fn split_at(&self, mid: usize) -> (SecVec<T>, SecVec<T>) {
+        let (head, tail) = self.content.split_at(mid);
+        (SecVec::from_slice_locked(head), SecVec::from_slice_locked(tail))
+    }
+
+    /// Move the contents to a freshly locked buffer of at least `new_cap`
+    /// capacity, then zero and unlock the old allocation before it is
+    /// freed. Does nothing if the current capacity is already sufficient.
+    /// All growth paths must reallocate through here so that no buffer that
+    /// ever held secret data leaves locked memory unwiped.
+    fn grow_to(&mut self, new_cap: usize) {
+        if new_cap <= self.content.capacity() {
+            return;
+        }
+        let mut new_content: Vec<T> = Vec::with_capacity(new_cap);
+        if self.locking {
+            self.locked = memlock::mlock(new_content.as_ptr(), new_content.capacity());
+        }
+        new_content.extend_from_slice(&self.content);
+        let mut old_content = std::mem::replace(&mut self.content, new_content);
+        let old_cap = old_content.capacity();
+        unsafe {
+            old_content.set_len(0);
+            mem::zero(old_content.as_mut_ptr(), old_cap);
+        }
+        if self.locking {
+            memlock::munlock(old_content.as_ptr(), old_cap);
+        }
+    }
+
+    /// Return the number of elements currently stored. Together with
+    /// `as_ptr` this is what a C function needs to read the secret.
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Whether the buffer currently holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// A raw pointer to the start of the locked buffer, for handing the
+    /// secret to a C function (OpenSSL, libsodium, …) over FFI together
+    /// with [`len`](Self::len).
+    ///
+    /// The pointer is only valid while this `SecVec` is alive and not
+    /// reallocated (any growing method may move the buffer), and the
+    /// pointee is zeroed on drop — the callee must not retain it. It is on
+    /// the caller to pick callees that don't copy the data somewhere
+    /// unlocked.
+    ///
+    /// ```no_run
+    /// # use secstr::SecStr;
+    /// extern "C" {
+    ///     fn EVP_Digest(data: *const u8, count: usize, /* … */);
+    /// }
+    /// let key = SecStr::from("correct horse battery staple");
+    /// unsafe { EVP_Digest(key.as_ptr(), key.len()) };
+    /// ```
+    pub fn as_ptr(&self) -> *const T {
+        self.content.as_ptr()
+    }
+
+    /// Mutable counterpart of [`as_ptr`](Self::as_ptr), for C functions
+    /// that write the secret (key derivation, random generation) straight
+    /// into the locked buffer. The same validity rules apply; the callee
+    /// must stay within `len` elements.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.content.as_mut_ptr()
+    }
+
+    /// Return the number of elements the buffer can hold without
+    /// reallocating.
+    pub fn capacity(&self) -> usize {
+        self.content.capacity()
+    }
+
+    /// The uninitialized spare capacity (`len..capacity`) as a mutable
+    /// slice, mirroring `Vec::spare_capacity_mut`. The whole capacity was
+    /// locked at allocation, so secret bytes can be produced (read from a
+    /// socket, written by a KDF) straight into locked memory and then
+    /// committed with [`set_len`](Self::set_len) — the zero-copy
+    /// fill-then-commit pattern, with no intermediate unlocked buffer.
+    pub fn spare_capacity_mut(&mut self) -> &mut [std::mem::MaybeUninit<T>] {
+        self.content.spare_capacity_mut()
+    }
+
+    /// Set the length to `new_len`, committing elements written into the
+    /// spare capacity; mirrors `Vec::set_len`.
+    ///
+    /// # Safety
+    ///
+    /// `new_len` must not exceed [`capacity`](Self::capacity), and the
+    /// elements `..new_len` must be initialized (e.g. through
+    /// [`spare_capacity_mut`](Self::spare_capacity_mut)).
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        self.content.set_len(new_len);
+        self.wiped = false;
+    }
+
+    /// Reserve capacity for at least `additional` more elements, locking
+    /// the grown buffer and zeroing/unlocking the old one if the contents
+    /// had to move. Over-allocates like `Vec::reserve` to amortize
+    /// repeated growth.
+    pub fn reserve(&mut self, additional: usize) {
+        // checked, like `Vec::reserve`: a wrapping add would turn an
+        // absurd request into a silent no-op instead of a panic
+        let needed = self
+            .content
+            .len()
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if needed > self.content.capacity() {
+            self.grow_to(std::cmp::max(needed, self.content.capacity() * 2));
+        }
+    }
+
+    /// Reserve capacity for exactly `additional` more elements, with the
+    /// same locking and wiping behavior as `reserve`.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.grow_to(self.content.len().checked_add(additional).expect("capacity overflow"));
+    }
+
+    /// Release unused capacity: move the contents to a tight, freshly
+    /// locked buffer and zero the *entire* old capacity (any part of it
+    /// may have held secret bytes at some point) before unlocking and
+    /// freeing it — where `Vec::shrink_to_fit` would hand the old buffer
+    /// back to the allocator still populated. Does nothing if the buffer
+    /// is already tight.
+    pub fn shrink_to_fit(&mut self) {
+        if self.content.capacity() == self.content.len() {
+            return;
+        }
+        let mut new_content: Vec<T> = Vec::with_capacity(self.content.len());
+        if self.locking {
+            self.locked = memlock::mlock(new_content.as_ptr(), new_content.capacity());
+        }
+        new_content.extend_from_slice(&self.content);
+        let mut old_content = std::mem::replace(&mut self.content, new_content);
+        let old_cap = old_content.capacity();
+        unsafe {
+            old_content.set_len(0);
+            mem::zero(old_content.as_mut_ptr(), old_cap);
+        }
+        if self.locking {
+            memlock::munlock(old_content.as_ptr(), old_cap);
+        }
+    }
+
+    /// Fallible version of [`reserve_exact`](Self::reserve_exact): returns
+    /// an error instead of aborting the process when the allocation fails,
+    /// for servers that must survive a huge untrusted length. On success
+    /// the grown buffer is locked and the old one zeroed and unlocked,
+    /// exactly like the infallible growth paths; on error the contents are
+    /// untouched.
+    pub fn try_reserve(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), std::collections::TryReserveError> {
+        // saturating: for non-ZSTs the saturated request fails cleanly
+        // inside `try_reserve_exact` (it exceeds `isize::MAX` bytes), and
+        // for ZSTs any capacity is satisfiable anyway
+        let needed = self.content.len().saturating_add(additional);
+        if needed <= self.content.capacity() {
+            return Ok(());
+        }
+        let mut new_content: Vec<T> = Vec::new();
+        new_content.try_reserve_exact(needed)?;
+        if self.locking {
+            self.locked = memlock::mlock(new_content.as_ptr(), new_content.capacity());
+        }
+        new_content.extend_from_slice(&self.content);
+        let mut old_content = std::mem::replace(&mut self.content, new_content);
+        let old_cap = old_content.capacity();
+        unsafe {
+            old_content.set_len(0);
+            mem::zero(old_content.as_mut_ptr(), old_cap);
+        }
+        if self.locking {
+            memlock::munlock(old_content.as_ptr(), old_cap);
+        }
+        Ok(())
+    }
+
+    /// Fallible constructor: allocate a locked, empty buffer of `capacity`
+    /// elements up front, returning an error instead of aborting if the
+    /// allocation fails. Pairs with [`try_reserve`](Self::try_reserve).
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, std::collections::TryReserveError> {
+        let mut content: Vec<T> = Vec::new();
+        content.try_reserve_exact(capacity)?;
+        let locked = memlock::mlock(content.as_ptr(), content.capacity());
+        Ok(SecVec {
+            content,
+            locked,
+            locking: true,
+            wiped: false,
+            #[cfg(feature = "audit")]
+            label: None,
+        })
+    }
+
+    /// Compare element-by-element by value, via `T`'s `PartialEq`, with no
+    /// early exit on a mismatch: the whole length is always walked, so for
+    /// primitive element types (whose own `==` is a plain flag-setting
+    /// comparison) the timing does not depend on the contents. This is
+    /// logical equality, where the byte-wise `PartialEq` is representation
+    /// equality. The length check still returns early; lengths are treated
+    /// as public.
+    #[must_use]
+    pub fn ct_eq_elements(&self, other: &SecVec<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        if self.content.len() != other.content.len() {
+            return false;
+        }
+        let mut acc = true;
+        for i in 0..self.content.len() {
+            acc &= self.content[i] == other.content[i];
+        }
+        acc
+    }
+
+    /// Overwrite every element in the current length with `value`, inside
+    /// the locked buffer, like `slice::fill`. The secure analogue for
+    /// scrubbing between uses or initializing before `copy_from_slice`;
+    /// capacity is left unchanged.
+    pub fn fill(&mut self, value: T) {
+        self.content.fill(value);
+    }
+
+    /// Overwrite the contents with a copy of `src`, inside the locked
+    /// buffer: no reallocation, no change to length or capacity. The
+    /// targeted replacement for `unsecure_mut().copy_from_slice(src)` when
+    /// re-deriving a secret of a known size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src.len() != self.unsecure().len()`, like
+    /// `slice::copy_from_slice`.
+    pub fn copy_from_slice(&mut self, src: &[T]) {
+        self.content.copy_from_slice(src);
+    }
+
+    /// Clone the secret, spelled out by name. Identical to `.clone()`, but
+    /// the call site reads as a deliberate duplication of secret material
+    /// rather than an incidental one hidden inside generic code — cloning
+    /// doubles the locked footprint and the leak surface, so codebases
+    /// auditing for that can grep for `clone` and convert intentional
+    /// sites to `explicit_clone`.
+    #[must_use = "cloning a secret doubles its locked footprint; drop the clone if it was unintended"]
+    pub fn explicit_clone(&self) -> Self {
+        self.clone()
+    }
+
+    /// Iterate over `chunk_size`-element chunks, for feeding a large
+    /// secret to a block-oriented API without copying each block into its
+    /// own buffer. The yielded slices are unsecured views borrowing
+    /// straight into the locked buffer (the last one may be shorter), with
+    /// the same exposure caveats as `unsecure`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero, like `slice::chunks`.
+    pub fn chunks(&self, chunk_size: usize) -> impl Iterator<Item = &[T]> {
+        self.content.chunks(chunk_size)
+    }
+
+    /// Iterate over all overlapping `size`-element windows, mirroring
+    /// `slice::windows` — for sliding scans over secret bytes (e.g.
+    /// finding a delimiter in assembled key material). Like
+    /// [`chunks`](Self::chunks), the yielded slices are unsecured views
+    /// borrowing straight into the locked buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero, like `slice::windows`.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = &[T]> {
+        self.content.windows(size)
+    }
+
+    /// View the contents as a slice of `N`-element arrays plus the
+    /// remainder, mirroring the (nightly) `slice::as_chunks` — so
+    /// block-oriented crypto can iterate `&[[u8; 16]]` blocks of a secret
+    /// with no copying and no allocation, the block size checked at
+    /// compile time. Both returned views borrow the locked buffer
+    /// directly; like [`unsecure`](Self::unsecure), they are unsecured
+    /// borrows — what the caller does with them is not this crate's
+    /// concern, and they must not outlive a reallocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N == 0`, like `slice::as_chunks`.
+    pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        assert!(N != 0, "as_chunks requires N != 0");
+        let full = self.content.len() / N;
+        let (head, tail) = self.content.split_at(full * N);
+        // SAFETY: `[T; N]` has the same layout as `N` consecutive `T`s,
+        // and `head` holds exactly `full * N` initialized elements.
+        let chunks = unsafe { std::slice::from_raw_parts(head.as_ptr() as *const [T; N], full) };
+        (chunks, tail)
+    }
+
+    /// Rotate the contents in place so that the element at `mid` moves to
+    /// the front, like `slice::rotate_left`, without exposing the slice:
+    /// the rotation stays confined to the locked buffer. Handy for
+    /// key-scheduling and nonce-management code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`, like `slice::rotate_left`.
+    pub fn rotate_left(&mut self, mid: usize) {
+        self.content.rotate_left(mid);
+    }
+
+    /// Rotate the contents in place so that the buffer's last `k` elements
+    /// move to the front, like `slice::rotate_right`; see
+    /// [`rotate_left`](Self::rotate_left).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > len`, like `slice::rotate_right`.
+    pub fn rotate_right(&mut self, k: usize) {
+        self.content.rotate_right(k);
+    }
+
+    /// Reverse the order of the elements in place, like `slice::reverse`,
+    /// without exposing the slice: the swaps stay confined to the locked
+    /// buffer. Occasionally needed for endianness fixes on key material.
+    pub fn reverse(&mut self) {
+        self.content.reverse();
+    }
+
+    /// Apply `f` to every element in place, inside the locked buffer: no
+    /// intermediate unlocked copy of the contents is made. Reads more
+    /// intentionally than going through `unsecure_mut().iter_mut()`.
+    pub fn map_in_place<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> T,
+    {
+        for item in self.content.iter_mut() {
+            *item = f(*item);
+        }
+    }
+
+    /// Iterate mutably over the elements, in place in the locked buffer:
+    /// transform secret bytes one at a time without grabbing the whole
+    /// slice through `unsecure_mut`. The yielded references point into
+    /// locked memory, but are unsecured views like every other borrow —
+    /// what the caller copies out of them is not this crate's concern.
+    /// Also available as `for item in &mut secret`.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.content.iter_mut()
+    }
+
+    /// Append `element`, growing through the zero-old-buffer reallocation
+    /// path when out of capacity.
+    pub fn push(&mut self, element: T) {
+        self.reserve(1);
+        self.content.push(element);
+        self.wiped = false;
+    }
+
+    /// Append all elements of `src`, growing through the zero-old-buffer
+    /// reallocation path when out of capacity.
+    pub fn extend_from_slice(&mut self, src: &[T]) {
+        self.reserve(src.len());
+        self.content.extend_from_slice(src);
+        self.wiped = false;
+    }
+
+    /// Insert `element` at position `index`, shifting all elements after
+    /// it to the right, like `Vec::insert`. Any reallocation goes through
+    /// the zero-old-buffer path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`, like `Vec::insert`.
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.reserve(1);
+        self.content.insert(index, element);
+        self.wiped = false;
+    }
+
+    /// Replace the elements in `range` with a copy of `replace_with`,
+    /// like `Vec::splice`, entirely within locked memory: any capacity
+    /// growth goes through the zero-old-buffer reallocation path *before*
+    /// the edit, and when the result shrinks the vacated tail is zeroed,
+    /// so no secret bytes linger either way. The removed elements are
+    /// overwritten rather than returned. A more general edit primitive
+    /// than [`insert`](Self::insert)/[`remove`](Self::remove).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is invalid or out of bounds, like `Vec::splice`.
+    pub fn splice<R>(&mut self, range: R, replace_with: &[T])
+    where
+        R: std::ops::RangeBounds<usize>,
+    {
+        use std::ops::Bound;
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.content.len(),
+        };
+        let old_len = self.content.len();
+        // saturating: an inverted range panics inside `Vec::splice` below,
+        // with the standard message, not here
+        let removed = end.saturating_sub(start);
+        if replace_with.len() > removed {
+            self.reserve(replace_with.len() - removed);
+        }
+        // capacity is sufficient now, so this edits in place
+        self.content.splice(start..end, replace_with.iter().copied());
+        let new_len = self.content.len();
+        if new_len < old_len {
+            // SAFETY: `new_len..old_len` is within the (unchanged)
+            // capacity and fully initialized.
+            unsafe { mem::zero(self.content.as_mut_ptr().add(new_len), old_len - new_len) };
+        }
+        if !replace_with.is_empty() {
+            self.wiped = false;
+        }
+    }
+
+    /// Remove and return the element at position `index`, shifting all
+    /// elements after it to the left, like `Vec::remove` — but zeroing the
+    /// vacated slot at the end so no stale copy of the shifted data stays
+    /// readable in the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, like `Vec::remove`.
+    pub fn remove(&mut self, index: usize) -> T {
+        let removed = self.content.remove(index);
+        let len = self.content.len();
+        // SAFETY: slot `len` is within the capacity (the buffer held
+        // `len + 1` elements a moment ago)
+        unsafe { mem::zero(self.content.as_mut_ptr().add(len), 1) };
+        removed
+    }
+
+    /// Return a copy of the element at `index`, or `None` if out of
+    /// bounds. Reads a single element without handing out a reference to
+    /// the whole slice.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.content.get(index).copied()
+    }
+
+    /// Remove and return the element at `index`, replacing it with the
+    /// last element, like `Vec::swap_remove` — but zeroing the vacated
+    /// slot at the end so no stale copy of the moved element stays
+    /// readable in the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, like `Vec::swap_remove`.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let removed = self.content.swap_remove(index);
+        let len = self.content.len();
+        // SAFETY: slot `len` is within the capacity (the buffer held
+        // `len + 1` elements a moment ago)
+        unsafe { mem::zero(self.content.as_mut_ptr().add(len), 1) };
+        removed
+    }
+
+    /// Keep only the elements for which `f` returns `true`, compacting
+    /// them toward the front inside the locked buffer like `Vec::retain`,
+    /// then zeroing the vacated tail. No reallocation occurs, so nothing
+    /// leaves locked memory.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let old_len = self.content.len();
+        self.content.retain(f);
+        let new_len = self.content.len();
+        // SAFETY: slots `new_len..old_len` are within the capacity.
+        unsafe { mem::zero(self.content.as_mut_ptr().add(new_len), old_len - new_len) };
+    }
+
+    /// Move all elements of `other` onto the end of `self`, like
+    /// `Vec::append`, then securely empty `other`: its buffer is zeroed and
+    /// truncated to length zero, but stays locked. Growth of `self` goes
+    /// through the usual zero-old-buffer reallocation path.
+    pub fn append(&mut self, other: &mut SecVec<T>) {
+        self.grow_to(self.content.len() + other.content.len());
+        self.content.extend_from_slice(&other.content);
+        self.wiped = false;
+        other.zero_out();
+    }
+
+    /// Resize the `SecVec` in-place so that its length is equal to
+    /// `new_len`, filling new space with `value`. When growing past the
+    /// current capacity, the contents are moved to a fresh locked buffer and
+    /// the old buffer is zeroed and unlocked before being freed; when
+    /// shrinking, the vacated `new_len..len` region is zeroed, so no secret
+    /// bytes linger in the retained capacity.
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        let old_len = self.content.len();
+        if new_len <= old_len {
+            self.content.truncate(new_len);
+            // SAFETY: `new_len..old_len` is within the (unchanged)
+            // capacity and fully initialized.
+            unsafe { mem::zero(self.content.as_mut_ptr().add(new_len), old_len - new_len) };
+            return;
+        }
+        self.grow_to(new_len);
+        self.content.resize(new_len, value);
+        self.wiped = false;
+    }
+
+    /// Overwrite the contents with `src` if `condition` is `true`; leave
+    /// them unchanged if it is `false`. Every byte of both buffers is
+    /// touched either way, so which of the two happened is not observable
+    /// through timing — useful for branchless protocol logic where the
+    /// condition itself is derived from secret data.
+    ///
+    /// The lengths must match and are treated as public.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src.len()` differs from the current length.
+    pub fn ct_assign_if(&mut self, condition: bool, src: &[T])
+    where
+        T: NoPaddingBytes,
+    {
+        let len = self.content.len();
+        assert_eq!(len, src.len(), "ct_assign_if requires equal lengths");
+        // SAFETY: both buffers contain `len` initialized elements, a `&mut
+        // self` + `&[T]` pair cannot overlap, the condition is 0 or 1, and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        unsafe { mem::copy_if(self.content.as_mut_ptr(), src.as_ptr(), len, condition as u8) }
+    }
+
+    /// Overwrite the string with zeros. This is automatically called in the destructor.
+    ///
+    /// (It also sets the length to 0.)
+    pub fn zero_out(&mut self) {
+        let cap = self.content.capacity();
+        // zero *before* truncating: if anything between the two steps ever
+        // panics, the destructor still runs on a buffer that has already
+        // been wiped, rather than skipping the still-populated capacity of
+        // a zero-length vec
+        unsafe {
+            #[cfg(feature = "random-wipe")]
+            mem::random_overwrite(self.content.as_mut_ptr(), cap);
+            mem::zero(self.content.as_mut_ptr(), cap);
+            self.content.set_len(0);
+        }
+        self.wiped = true;
+    }
+
+    /// Turn the secret back into a regular `Vec`, unprotected: the buffer
+    /// is unlocked and handed over as-is (not copied, not zeroed), and the
+    /// destructor does not run. Mirrors `SecUtf8::into_unsecure`.
+    ///
+    /// This is an escape hatch for APIs that insist on taking ownership of
+    /// a plain `Vec`. From the moment it returns, nothing protects the
+    /// contents anymore — they can reach swap and core dumps, and *zeroing
+    /// the `Vec` after use is entirely on the recipient*, including after
+    /// any reallocation it performs.
+    #[must_use]
+    pub fn into_unsecure(self) -> Vec<T> {
+        self.take_content()
+    }
+
+    /// Convert to the boxed-slice form, keeping the lock/zero guarantees:
+    /// when the buffer is already tight (`len == capacity`) the very same
+    /// allocation is handed to the [`SecBoxedSlice`] with no copy; when it
+    /// is not, the contents move through the usual shrinking path, which
+    /// locks the new buffer before the copy and zeroes and unlocks the old
+    /// one. For APIs that prefer `Box<[T]>` semantics — fixed length, no
+    /// spare capacity. [`SecBoxedSlice::into_sec_vec`] converts back.
+    ///
+    /// [`SecBoxedSlice`]: struct.SecBoxedSlice.html
+    /// [`SecBoxedSlice::into_sec_vec`]: struct.SecBoxedSlice.html#method.into_sec_vec
+    pub fn into_boxed_slice(mut self) -> SecBoxedSlice<T> {
+        self.shrink_to_fit();
+        // `len == capacity` now, so `into_boxed_slice` does not reallocate
+        SecBoxedSlice::new(self.take_content().into_boxed_slice())
+    }
+
+    /// The one place allowed to move the buffer out of a `SecVec`.
+    /// Invariant: the buffer is unlocked exactly once (matching the lock
+    /// taken at construction), and `self` is forgotten so `Drop` neither
+    /// wipes the handed-over buffer nor unlocks it a second time. Every
+    /// `into_*` escape hatch must go through here rather than hand-rolling
+    /// the replace/forget dance.
+    fn take_content(mut self) -> Vec<T> {
+        if self.locking {
+            memlock::munlock(self.content.as_ptr(), self.content.capacity());
+        }
+        let content = std::mem::take(&mut self.content);
+        std::mem::forget(self);
+        content
+    }
+}
+
+impl SecVec<u8> {
+    /// Copy the bytes of `s` into a freshly locked buffer, then wipe the
+    /// source string's full capacity and clear it. For ingesting a secret
+    /// that unavoidably arrived in an unlocked `String` the caller no
+    /// longer needs — the source stops holding a readable copy as soon as
+    /// this returns.
+    pub fn from_str_zeroing(s: &mut String) -> SecStr {
+        let mut content = Vec::with_capacity(s.len());
+        let locked = memlock::mlock(content.as_ptr(), content.capacity());
+        content.extend_from_slice(s.as_bytes());
+        let sec = SecVec {
+            content,
+            locked,
+            locking: true,
+            wiped: false,
+            #[cfg(feature = "audit")]
+            label: None,
+        };
+        // SAFETY: zeroed bytes are valid `u8`s (and valid UTF-8 once the
+        // length is cut to 0)
+        unsafe {
+            let source = s.as_mut_vec();
+            let cap = source.capacity();
+            mem::zero(source.as_mut_ptr(), cap);
+            source.set_len(0);
+        }
+        sec
+    }
+
+    /// Compare for equality, in constant time, against anything that
+    /// borrows as bytes — another `SecStr`, a `Vec<u8>`, a `&[u8]`, a
+    /// fixed-size array: one generic entry point for the long tail of
+    /// operand types the dedicated `PartialEq` impls don't cover. Same
+    /// semantics as those impls: no early exit on a content difference,
+    /// immediate `false` on a length mismatch (lengths are public).
+    #[must_use]
+    pub fn ct_eq_bytes<B: Borrow<[u8]>>(&self, other: B) -> bool {
+        constant_time_eq(&self.content, other.borrow())
+    }
+
+    /// Whether the secret starts with `prefix`, compared in
+    /// content-constant time: a long-enough secret always has its leading
+    /// `prefix.len()` bytes compared with no early exit, so the position
+    /// of a mismatch does not leak. The prefix length is public (a too-
+    /// short secret returns `false` immediately), as is the prefix itself
+    /// — typically protocol framing like a version byte or key-ID tag.
+    #[must_use]
+    pub fn ct_starts_with(&self, prefix: &[u8]) -> bool {
+        if self.content.len() < prefix.len() {
+            return false;
+        }
+        constant_time_eq(&self.content[..prefix.len()], prefix)
+    }
+
+    /// Whether the secret ends with `suffix`; the trailing counterpart of
+    /// [`ct_starts_with`](Self::ct_starts_with), for protocols that carry
+    /// a trailing tag. Same contract: content-constant time, suffix length
+    /// public.
+    #[must_use]
+    pub fn ct_ends_with(&self, suffix: &[u8]) -> bool {
+        if self.content.len() < suffix.len() {
+            return false;
+        }
+        constant_time_eq(&self.content[self.content.len() - suffix.len()..], suffix)
+    }
+
+    /// How many leading bytes of `self` and `other` match — for debugging
+    /// credential mismatches in tests, and nothing else. **Deliberately
+    /// not constant time** (it exists to reveal the mismatch position),
+    /// which is why it only compiles with `debug_assertions`: it cannot be
+    /// called from a release build, so it cannot end up on a production
+    /// code path.
+    #[cfg(debug_assertions)]
+    #[must_use]
+    pub fn debug_common_prefix_len(&self, other: &SecStr) -> usize {
+        self.content
+            .iter()
+            .zip(&other.content)
+            .take_while(|(a, b)| a == b)
+            .count()
+    }
+
+    /// Compare with `other` lexicographically, like `Ord` on byte slices,
+    /// but in a loop with no early exit and no data-dependent branches:
+    /// the runtime depends on both lengths, never on the contents. Ties
+    /// over the common prefix are broken by length, matching slice
+    /// ordering. Useful for keeping secret collections in a deterministic
+    /// order without leaking content through timing.
+    #[must_use]
+    pub fn ct_cmp(&self, other: &SecStr) -> std::cmp::Ordering {
+        let a = &self.content;
+        let b = &other.content;
+        let min_len = std::cmp::min(a.len(), b.len());
+        // -1, 0 or 1; only the first difference may set it, selected by
+        // masking instead of branching
+        let mut result: i32 = 0;
+        for i in 0..min_len {
+            let lt = (a[i] < b[i]) as i32;
+            let gt = (a[i] > b[i]) as i32;
+            let seen = ((result != 0) as i32).wrapping_neg();
+            result = (seen & result) | (!seen & (gt - lt));
+        }
+        let len_ord = (a.len() > b.len()) as i32 - ((a.len() < b.len()) as i32);
+        let seen = ((result != 0) as i32).wrapping_neg();
+        result = (seen & result) | (!seen & len_ord);
+        result.cmp(&0)
+    }
+
+    /// Compare with `other` without revealing either secret's length
+    /// through the comparison: both operands are copied into zero-padded
+    /// locked scratch buffers of `pad_to` bytes, all `pad_to` bytes are
+    /// compared, and the length difference is folded into the result
+    /// without short-circuiting.
+    ///
+    /// Guarantee: the comparison loop runs over exactly `pad_to` bytes
+    /// regardless of either length or any content, and no branch depends on
+    /// the contents. The initial copies into the scratch buffers still take
+    /// time proportional to the actual lengths, so length hiding is only as
+    /// good as a `memcpy`'s timing signal — but content never affects
+    /// timing. This costs two `pad_to`-byte locked allocations and a full
+    /// `pad_to`-byte pass, versus the default `PartialEq` which exits
+    /// early on length mismatch and compares only `len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either operand is longer than `pad_to` (the bound is
+    /// public).
+    #[must_use]
+    pub fn ct_eq_padded(&self, other: &SecStr, pad_to: usize) -> bool {
+        assert!(
+            self.content.len() <= pad_to && other.content.len() <= pad_to,
+            "ct_eq_padded operands must not be longer than pad_to"
+        );
+        let mut ours = SecStr::new(vec![0u8; pad_to]);
+        let mut theirs = SecStr::new(vec![0u8; pad_to]);
+        ours.content[..self.content.len()].copy_from_slice(&self.content);
+        theirs.content[..other.content.len()].copy_from_slice(&other.content);
+        // SAFETY: both scratch buffers contain `pad_to` initialized bytes.
+        let bytes_eq = unsafe { mem::cmp(ours.content.as_ptr(), theirs.content.as_ptr(), pad_to) };
+        // `&` instead of `&&`: no short-circuit on the length comparison
+        bytes_eq & (self.content.len() == other.content.len())
+    }
+}
+
+// Creation
+impl<T, U> From<U> for SecVec<T>
+where
+    U: Into<Vec<T>>,
+    T: Sized + Copy,
+{
+    fn from(s: U) -> SecVec<T> {
+        SecVec::new(s.into())
+    }
+}
+
+impl FromStr for SecStr {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<SecStr, ()> {
+        Ok(SecStr::new(s.into()))
+    }
+}
+
+impl<T> Default for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    /// An empty, but still locked, `SecVec` (locking a zero-length buffer
+    /// is a no-op). Mainly useful for `#[derive(Default)]` on structs with
+    /// secret fields.
+    fn default() -> Self {
+        SecVec::new(Vec::new())
+    }
+}
+
+// Cloning
+impl<T> Clone for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn clone(&self) -> Self {
+        // `from_slice_locked` locks the new buffer *before* copying the
+        // secret into it, so the copy never sits in unlocked memory
+        #[cfg(feature = "audit")]
+        {
+            let mut new = SecVec::from_slice_locked(&self.content);
+            new.label = self.label.clone();
+            new
+        }
+        #[cfg(not(feature = "audit"))]
+        SecVec::from_slice_locked(&self.content)
+    }
+}
+
+// Collecting — rounds out the collection-trait surface alongside `push`
+// and `extend_from_slice`; all growth still goes through the
+// zero-old-buffer reallocation path
+impl<T> Extend<T> for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut SecVec<T>
+where
+    T: Sized + Copy,
+{
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Extend<&'a T> for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+// Borrowing
+impl<T> Borrow<[T]> for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn borrow(&self) -> &[T] {
+        self.content.borrow()
+    }
+}
+
+impl<T> BorrowMut<[T]> for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.content.borrow_mut()
+    }
+}
+
+// Indexing
+impl<T, U> std::ops::Index<U> for SecVec<T>
+where
+    T: Sized + Copy,
+    Vec<T>: std::ops::Index<U>,
+{
+    type Output = <Vec<T> as std::ops::Index<U>>::Output;
+
+    fn index(&self, index: U) -> &Self::Output {
+        std::ops::Index::index(&self.content, index)
+    }
+}
+
+// The returned place is an unsecured view of locked memory, same as
+// `unsecure_mut`
+impl<T, U> std::ops::IndexMut<U> for SecVec<T>
+where
+    T: Sized + Copy,
+    Vec<T>: std::ops::IndexMut<U>,
+{
+    fn index_mut(&mut self, index: U) -> &mut Self::Output {
+        std::ops::IndexMut::index_mut(&mut self.content, index)
+    }
+}
+
+// Comparisons
+impl<T> PartialEq for SecVec<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    /// Representation equality: the raw bytes of the two buffers are
+    /// compared, in constant time. For multi-byte `T` this matches logical
+    /// equality on any one machine, but bytes round-tripped through
+    /// serialization across endianness should be compared by value with
+    /// [`ct_eq_elements`](struct.SecVec.html#method.ct_eq_elements)
+    /// instead.
+    ///
+    /// Only `len` elements take part: the buffers' *capacities* never
+    /// affect the result or the timing. Lengths are treated as public —
+    /// a length mismatch returns early; for callers whose lengths are
+    /// themselves secret, see
+    /// [`ct_eq_padded`](struct.SecVec.html#method.ct_eq_padded).
+    fn eq(&self, other: &SecVec<T>) -> bool {
+        let len = self.content.len();
+        if len != other.content.len() {
+            return false;
+        }
+        // SAFETY: both buffers contain `len` initialized elements and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        unsafe { mem::cmp(self.content.as_ptr(), other.content.as_ptr(), len) }
+    }
+}
+
+impl<T> Eq for SecVec<T> where T: Sized + Copy + NoPaddingBytes {}
+
+// Ergonomic comparisons against plain slices (mostly literals in tests and
+// assertions). The borrowed side is assumed non-secret, but the comparison
+// still runs in constant time so the secret side's contents don't leak —
+// only the length check exits early, as everywhere else in the crate.
+impl<T> PartialEq<[T]> for SecVec<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        constant_time_eq_slices(&self.content, other)
+    }
+}
+
+impl<T> PartialEq<&[T]> for SecVec<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn eq(&self, other: &&[T]) -> bool {
+        constant_time_eq_slices(&self.content, other)
+    }
+}
+
+/// Shared body of the slice comparisons above.
+fn constant_time_eq_slices<T: Sized + Copy + NoPaddingBytes>(a: &[T], b: &[T]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // SAFETY: both slices contain `a.len()` initialized elements and
+    // `T: NoPaddingBytes` guarantees a padding-free representation.
+    unsafe { mem::cmp(a.as_ptr(), b.as_ptr(), a.len()) }
+}
+
+#[cfg(feature = "subtle")]
+impl<T> subtle::ConstantTimeEq for SecVec<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        let len = self.content.len();
+        if len != other.content.len() {
+            return subtle::Choice::from(0);
+        }
+        // SAFETY: both buffers contain `len` initialized elements and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        let diff = unsafe { mem::diff(self.content.as_ptr(), other.content.as_ptr(), len) };
+        // fold the accumulated difference to 0/1 without branching on it:
+        // `diff | -diff` has the high bit set iff `diff` is non-zero
+        subtle::Choice::from(1 ^ ((diff | diff.wrapping_neg()) >> 7))
+    }
+}
+
+// Hashing — consistent with the constant-time `Eq`, so `SecStr` works as
+// a `HashMap` key (e.g. allow-list lookups) with or without libsodium.
+// Without libsodium the raw bytes are fed to the `Hasher`, so pick a
+// hasher you trust with them; with libsodium only a generichash digest is.
+// The `NoPaddingBytes` bound matches `Eq` (which map keys need anyway) and
+// is load-bearing: it keeps uninitialized padding bytes out of the byte
+// view below.
+#[cfg(not(feature = "libsodium-sys"))]
+impl<T> std::hash::Hash for SecVec<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // SAFETY: the buffer holds `len` initialized elements, and
+        // `T: NoPaddingBytes` means every byte of them is initialized.
+        state.write(unsafe {
+            std::slice::from_raw_parts(
+                self.content.as_ptr() as *const u8,
+                self.content.len() * std::mem::size_of::<T>(),
+            )
+        });
+    }
+}
+
+// (the digest hides the contents from the `Hasher`; the bound keeps the
+// API, and the no-padding guarantee, identical across backends)
+#[cfg(feature = "libsodium-sys")]
+impl<T> std::hash::Hash for SecVec<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let mut hashed = [0u8; libsodium_sys::crypto_generichash_BYTES as usize];
+        unsafe {
+            libsodium_sys::crypto_generichash(
+                hashed.as_mut_ptr(),
+                hashed.len(),
+                self.content.as_ptr() as *const u8,
+                (self.content.len() * std::mem::size_of::<T>()) as u64,
+                std::ptr::null(),
+                0,
+            );
+        }
+        state.write(&hashed);
+    }
+}
+
+// Make sure sensitive information is not logged accidentally
+impl<T> fmt::Debug for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl<T> fmt::Display for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+// Delete sensitive information from memory
+impl<T> Drop for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn drop(&mut self) {
+        self.zero_out();
+        // SAFETY: `zero_out` left `capacity` zero-initialized elements
+        #[cfg(feature = "verify-zero")]
+        unsafe {
+            mem::verify_zeroed(self.content.as_ptr(), self.content.capacity());
+        }
+        if self.locking {
+            memlock::munlock(self.content.as_ptr(), self.content.capacity());
+        }
+    }
+}
+
+// Writing: `write!` and friends can accumulate formatted secret material
+// directly into the locked buffer, never materializing it in an unlocked
+// `String`/`Vec`
+impl std::io::Write for SecStr {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// Interop with the `secrecy` crate, for projects standardized on its
+// `ExposeSecret`/`Secret` types that want this crate's memory protections
+// underneath
+#[cfg(feature = "secrecy")]
+impl secrecy::ExposeSecret<Vec<u8>> for SecStr {
+    fn expose_secret(&self) -> &Vec<u8> {
+        &self.content
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl From<SecStr> for secrecy::Secret<Vec<u8>> {
+    /// Hand the backing buffer over to a `Secret`, which keeps the
+    /// zero-on-drop guarantee (via `Zeroize`) but gives up the `mlock`
+    /// protection.
+    fn from(s: SecStr) -> Self {
+        secrecy::Secret::new(s.take_content())
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl SecVec<u8> {
+    /// Copy the contents of a `secrecy::Secret` byte vector into a freshly
+    /// locked buffer. (A copy is unavoidable: `Secret` only hands out
+    /// borrowed views.)
+    pub fn from_secret(s: &secrecy::Secret<Vec<u8>>) -> SecStr {
+        use secrecy::ExposeSecret;
+        let mut content = Vec::with_capacity(s.expose_secret().len());
+        let locked = memlock::mlock(content.as_ptr(), content.capacity());
+        content.extend_from_slice(s.expose_secret());
+        SecVec {
+            content,
+            locked,
+            locking: true,
+            wiped: false,
+            #[cfg(feature = "audit")]
+            label: None,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SecStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.unsecure())
+    }
+}
+
+#[cfg(feature = "serde")]
+struct BytesVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = SecStr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("byte array")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(SecStr::from(v))
+    }
+
+    // formats without a native bytes type (e.g. JSON) present the field
+    // as a sequence of integers; read those straight into a pre-sized
+    // locked buffer. The size hint is attacker-controlled in formats that
+    // report the declared element count (e.g. CBOR), so cap what gets
+    // preallocated on its say-so — a genuinely longer secret just grows
+    // through the usual reallocation path as elements arrive.
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut sec = SecStr::new(Vec::new());
+        sec.reserve(std::cmp::min(seq.size_hint().unwrap_or(0), 4096));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            sec.push(byte);
+        }
+        Ok(sec)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SecStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+/// A data type suitable for storing sensitive information such as passwords and private keys in memory, that implements:
+///
+/// - Automatic zeroing in `Drop`
+/// - Constant time comparison in `PartialEq` (does not short circuit on the first different character; but terminates instantly if strings have different length)
+/// - Outputting `***SECRET***` to prevent leaking secrets into logs with `fmt::Debug` and `fmt::Display`
+/// - Automatic `mlock` to protect against leaking into swap (any unix)
+/// - Automatic `madvise(MADV_NOCORE/MADV_DONTDUMP)` to protect against leaking into core dumps (FreeBSD, DragonFlyBSD, Linux)
+///
+/// Just like `SecStr`, but with the UTF-8 invariant of `String` preserved,
+/// so the contents can be borrowed as a `&str`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecUtf8(SecVec<u8>);
+
+impl SecUtf8 {
+    /// Borrow the contents of the string.
+    #[must_use]
+    pub fn unsecure(&self) -> &str {
+        // SAFETY: `SecUtf8` is only ever constructed from valid UTF-8 and
+        // never exposes `&mut [u8]` access that could break the invariant.
+        unsafe { std::str::from_utf8_unchecked(self.0.unsecure()) }
+    }
+
+    /// Validate the already-secured bytes of `v` as UTF-8 in place and
+    /// wrap them without copying: on success the same locked buffer is
+    /// reused, so the secret never round-trips through an unlocked
+    /// `String`. On failure the untouched `SecVec` is handed back along
+    /// with the error, so the caller keeps the secured bytes.
+    pub fn from_sec_vec(v: SecVec<u8>) -> Result<SecUtf8, (SecVec<u8>, std::str::Utf8Error)> {
+        match std::str::from_utf8(v.unsecure()) {
+            Ok(_) => Ok(SecUtf8(v)),
+            Err(e) => Err((v, e)),
+        }
+    }
+
+    /// Compare with `other` in the same content-constant-time way as
+    /// `SecStr::ct_cmp`, over the UTF-8 bytes. For valid UTF-8, byte order
+    /// equals codepoint order, so the result matches `str` ordering.
+    #[must_use]
+    pub fn ct_cmp(&self, other: &SecUtf8) -> std::cmp::Ordering {
+        self.0.ct_cmp(&other.0)
+    }
+
+    /// Remove leading and trailing ASCII whitespace in place, inside the
+    /// secured buffer: the trimmed contents are shifted to the front and
+    /// the vacated tail is zeroed, with no `&str` exposure and no re-wrap.
+    /// For scrubbing the trailing newline off a password read from a file
+    /// or pipe.
+    ///
+    /// Restricted to ASCII whitespace: it can be stripped byte-wise
+    /// without re-validating UTF-8 boundaries.
+    pub fn trim_in_place(&mut self) {
+        let bytes = &self.0.content;
+        let old_len = bytes.len();
+        let start = bytes
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(old_len);
+        let end = bytes
+            .iter()
+            .rposition(|b| !b.is_ascii_whitespace())
+            .map_or(start, |i| i + 1);
+        let trimmed = end - start;
+        self.0.content.copy_within(start..end, 0);
+        self.0.content.truncate(trimmed);
+        // SAFETY: `trimmed..old_len` is within the (unchanged) capacity
+        // and fully initialized.
+        unsafe { mem::zero(self.0.content.as_mut_ptr().add(trimmed), old_len - trimmed) };
+    }
+
+    /// Copy `s` into a secured buffer with its leading and trailing ASCII
+    /// whitespace already stripped — [`trim_in_place`](Self::trim_in_place)
+    /// fused into the constructor, so the untrimmed form is never secured
+    /// and then shifted. (The caller's `&str` is borrowed and keeps its
+    /// own unprotected copy, like `SecUtf8::from`.)
+    pub fn from_trimmed(s: &str) -> SecUtf8 {
+        SecUtf8::from(s.trim_matches(|c: char| c.is_ascii_whitespace()))
+    }
+
+    /// Secure an owned `String` with the zero-copy contract made
+    /// explicit: the exact backing buffer of `s` is the one that gets
+    /// locked, with no reallocation (testable by pointer identity).
+    /// `SecUtf8::from` does the same thing for an owned `String`, but
+    /// accepts borrows too, where it silently copies; use this name when
+    /// the no-copy guarantee matters.
+    ///
+    /// The usual caveat applies: any reallocations `String` performed
+    /// while the value was being built, and any copies the caller made
+    /// before handing it over, are out of this crate's hands. To also
+    /// wipe a `String` the caller keeps, see `SecStr::from_str_zeroing`.
+    pub fn consume_string(s: String) -> SecUtf8 {
+        SecUtf8(SecVec::new(s.into_bytes()))
+    }
+
+    /// Read a secret from the environment variable `key` into a secured
+    /// buffer, wipe the intermediate `String`, and remove the variable
+    /// from this process's environment so child processes don't inherit
+    /// it and later code can't re-read it.
+    ///
+    /// Platform honesty: the bytes of the *original* environment entry
+    /// cannot be scrubbed portably — `unsetenv` only unlinks the entry,
+    /// the inherited `environ` block keeps its contents in (unlocked)
+    /// process memory. This helper shortens the value's lifetime and
+    /// secures the copy you actually use; it cannot retroactively protect
+    /// what `exec` put on the stack. Call it early, before spawning
+    /// threads: modifying the environment concurrently with reads is
+    /// undefined behavior on POSIX.
+    pub fn from_env(key: &str) -> Result<SecUtf8, std::env::VarError> {
+        let mut value = std::env::var(key)?;
+        let sec = SecUtf8(SecStr::from_str_zeroing(&mut value));
+        std::env::remove_var(key);
+        Ok(sec)
+    }
+
+    /// Compare with `other` without short-circuiting on a length mismatch,
+    /// for the threat model where the password's *length* is itself
+    /// sensitive: the default `==` returns early when the lengths differ,
+    /// telling a timing observer whether the candidate was the right
+    /// length. Here the length difference is folded into the accumulator
+    /// and the byte loop always runs over the longer operand, reading both
+    /// sides (through clamped in-bounds indices) every iteration with no
+    /// content- or match-dependent branch.
+    ///
+    /// The exact guarantee: the runtime depends only on
+    /// `max(self.len(), other.len())` — never on the contents, and never
+    /// on whether the lengths (or bytes) matched. The maximum itself is
+    /// still observable; to hide even that, compare under a fixed public
+    /// bound with [`SecStr::ct_eq_padded`]. Slower than `==`: no early
+    /// exit, and a masked two-sided read per position.
+    ///
+    /// [`SecStr::ct_eq_padded`]: struct.SecVec.html#method.ct_eq_padded
+    #[must_use]
+    pub fn ct_eq_hide_len(&self, other: &SecUtf8) -> bool {
+        let a = &self.0.content;
+        let b = &other.0.content;
+        let (la, lb) = (a.len(), b.len());
+        let mut acc = la ^ lb;
+        // one-byte stand-ins keep the clamped reads of an empty operand in
+        // bounds; the real lengths still drive the masks below
+        let zero = [0u8];
+        let a: &[u8] = if la == 0 { &zero } else { a };
+        let b: &[u8] = if lb == 0 { &zero } else { b };
+        for i in 0..std::cmp::max(la, lb) {
+            let av = a[std::cmp::min(i, a.len() - 1)];
+            let bv = b[std::cmp::min(i, b.len() - 1)];
+            // positions past either operand's end carry no byte
+            // information — the length fold above already recorded the
+            // mismatch — so mask them out instead of branching
+            let in_both = (((i < la) & (i < lb)) as u8).wrapping_neg();
+            acc |= ((av ^ bv) & in_both) as usize;
+        }
+        acc == 0
+    }
+
+    /// Read one line of secret input (a password from a pipe or terminal)
+    /// straight into a secured buffer: bytes go from the reader's buffer
+    /// to locked memory, the trailing `\n` or `\r\n` is stripped, and no
+    /// intermediate unlocked `String` is ever built — unlike
+    /// `BufRead::read_line`. EOF before a newline yields whatever was
+    /// read; an empty line (or immediate EOF) yields an empty secret.
+    ///
+    /// The reader's own internal buffer (e.g. `BufReader`'s) is unlocked
+    /// and out of this crate's hands, like every copy made before the
+    /// bytes reach us. Invalid UTF-8 fails with `InvalidData`; the
+    /// secured bytes read so far are wiped on the way out.
+    pub fn read_line<R: std::io::BufRead>(r: &mut R) -> std::io::Result<SecUtf8> {
+        let mut sec = SecStr::new(Vec::new());
+        let mut saw_newline = false;
+        loop {
+            let (done, used) = {
+                let available = r.fill_buf()?;
+                if available.is_empty() {
+                    (true, 0) // EOF
+                } else {
+                    match available.iter().position(|b| *b == b'\n') {
+                        Some(i) => {
+                            sec.extend_from_slice(&available[..i]);
+                            saw_newline = true;
+                            (true, i + 1)
+                        }
+                        None => {
+                            sec.extend_from_slice(available);
+                            (false, available.len())
+                        }
+                    }
+                }
+            };
+            r.consume(used);
+            if done {
+                break;
+            }
+        }
+        // the `\r` is only framing when a `\n` followed it; at EOF it is
+        // the secret's own last byte
+        if saw_newline && sec.last() == Some(b'\r') {
+            // `resize` zeroes the vacated byte
+            let len = sec.len();
+            sec.resize(len - 1, 0);
+        }
+        SecUtf8::from_sec_vec(sec)
+            .map_err(|(_, e)| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Compare with `other` for equality up to ASCII case, in constant
+    /// time: both sides are case-folded branchlessly as they are compared,
+    /// inside secured memory, with no early exit on a content mismatch
+    /// (only the length check exits early; lengths are treated as public).
+    /// For credential systems that treat secured usernames
+    /// case-insensitively without leaking them through a `to_lowercase`
+    /// allocation.
+    ///
+    /// Folding is deliberately restricted to ASCII: full Unicode case
+    /// folding maps codepoints to varying numbers of codepoints, which is
+    /// inherently data-dependent and can't be done in constant time.
+    #[must_use]
+    pub fn ct_eq_ignore_ascii_case(&self, other: &SecUtf8) -> bool {
+        // `b | 0x20` for uppercase ASCII only, computed without branching
+        fn fold(b: u8) -> u8 {
+            b | (((b.wrapping_sub(b'A') < 26) as u8) << 5)
+        }
+        let a = &self.0.content;
+        let b = &other.0.content;
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut result: u8 = 0;
+        for i in 0..a.len() {
+            result |= std::hint::black_box(fold(a[i]) ^ fold(b[i]));
+        }
+        result == 0
+    }
+
+    /// Turn the string into a regular `String` again, unprotected.
+    #[must_use]
+    pub fn into_unsecure(self) -> String {
+        // SAFETY: see `unsecure`; the bytes were produced by a `String`.
+        unsafe { String::from_utf8_unchecked(self.0.take_content()) }
+    }
+}
+
+impl<U> From<U> for SecUtf8
+where
+    U: Into<String>,
+{
+    fn from(s: U) -> SecUtf8 {
+        SecUtf8(SecVec::new(s.into().into_bytes()))
+    }
+}
+
+impl std::iter::FromIterator<char> for SecUtf8 {
+    /// Collect an iterator of `char`s (e.g. a filtered passphrase)
+    /// directly into a secured buffer, UTF-8-encoding each char in place:
+    /// no intermediate unlocked `String` is built, and any growth goes
+    /// through the zero-old-buffer reallocation path.
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> SecUtf8 {
+        let iter = iter.into_iter();
+        let mut sec = SecVec::new(Vec::new());
+        sec.reserve(iter.size_hint().0);
+        let mut utf8 = [0u8; 4];
+        for c in iter {
+            sec.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+        }
+        // the stack scratch buffer held up to one char of the secret
+        // SAFETY: `utf8` is a live, initialized array.
+        unsafe { mem::zero(utf8.as_mut_ptr(), utf8.len()) };
+        SecUtf8(sec)
+    }
+}
+
+impl Default for SecUtf8 {
+    /// An empty, but still locked, `SecUtf8`. Mainly useful for
+    /// `#[derive(Default)]` on structs with secret fields.
+    fn default() -> Self {
+        SecUtf8(SecVec::default())
+    }
+}
+
+impl FromStr for SecUtf8 {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<SecUtf8, ()> {
+        Ok(SecUtf8::from(s))
+    }
+}
+
+// Comparisons against the raw-byte representation, still constant time
+impl PartialEq<SecStr> for SecUtf8 {
+    fn eq(&self, other: &SecStr) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<SecUtf8> for SecStr {
+    fn eq(&self, other: &SecUtf8) -> bool {
+        *self == other.0
+    }
+}
+
+// Ergonomic comparisons against plain strings (mostly literals); the
+// borrowed side is assumed non-secret, the comparison is still constant
+// time over the UTF-8 bytes.
+impl PartialEq<str> for SecUtf8 {
+    fn eq(&self, other: &str) -> bool {
+        constant_time_eq(self.unsecure().as_bytes(), other.as_bytes())
+    }
+}
+
+impl PartialEq<&str> for SecUtf8 {
+    fn eq(&self, other: &&str) -> bool {
+        constant_time_eq(self.unsecure().as_bytes(), other.as_bytes())
+    }
+}
+
+// Hashing — forwarded to the inner `SecVec<u8>`, so `SecUtf8` hashes
+// exactly like the `SecStr` of its UTF-8 bytes under either backend (raw
+// bytes to the `Hasher` without libsodium, a generichash digest with it)
+// and works as a map key at parity with `SecStr`.
+impl std::hash::Hash for SecUtf8 {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+// Opt-in `str` view (the `deref` feature): lets any `str`-taking API
+// reach the secret through an ordinary trait bound — which is exactly why
+// it is not on by default. Anything going through this view uses `str`'s
+// own operations, which are *not* constant time; only the dedicated
+// comparison methods carry that guarantee.
+//
+// Deliberately `AsRef` and not `Borrow`: `Borrow<str>` requires `Hash` to
+// agree with `str`'s, and `SecUtf8` hashes like the `SecStr` of its bytes
+// (under libsodium, a digest of them) — so `HashMap::get(&str)` on a
+// `SecUtf8`-keyed map would quietly never find anything. Content-keyed
+// maps must use the full `SecUtf8` as the lookup key.
+#[cfg(feature = "deref")]
+impl AsRef<str> for SecUtf8 {
+    fn as_ref(&self) -> &str {
+        self.unsecure()
+    }
+}
+
+// Make sure sensitive information is not logged accidentally
+impl fmt::Debug for SecUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl fmt::Display for SecUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SecUtf8 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.unsecure())
+    }
+}
+
+#[cfg(feature = "serde")]
+struct SecUtf8Visitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for SecUtf8Visitor {
+    type Value = SecUtf8;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an utf-8 encoded string")
+    }
+
+    fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(SecUtf8::from(v))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(SecUtf8::from(v))
+    }
+
+    fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(SecUtf8::from(v))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        // keep the offending bytes out of the error: they may be a secret
+        std::str::from_utf8(v)
+            .map(SecUtf8::from)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Other("invalid utf-8 byte sequence"), &self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SecUtf8 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_string(SecUtf8Visitor)
+    }
+}
+
+/// Serde glue for optional secret fields where the empty string means "no
+/// secret", for use with `#[serde(with = "secstr::serde_opt")]`:
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Config {
+///     #[serde(default, with = "secstr::serde_opt")]
+///     db_password: Option<SecUtf8>,
+/// }
+/// ```
+///
+/// Deserializing `""` or `null` (or, with `#[serde(default)]`, an absent
+/// field) yields `None`; `None` serializes back as `""`, which keeps the
+/// round trip stable for formats without optional fields. If an empty
+/// string should count as a real secret, use a plain `Option<SecUtf8>`
+/// field without this adapter.
+#[cfg(feature = "serde")]
+pub mod serde_opt {
+    use super::SecUtf8;
+    use serde::de::{Deserialize, Deserializer};
+    use serde::ser::Serializer;
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<SecUtf8>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(v) => serializer.serialize_str(v.unsecure()),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<SecUtf8>, D::Error> {
+        // the string goes through `SecUtf8`'s own deserializer, so it is
+        // secured before the emptiness check ever looks at it
+        let secret: Option<SecUtf8> = Option::deserialize(deserializer)?;
+        Ok(secret.filter(|s| !s.unsecure().is_empty()))
+    }
+}
+
+/// A `fmt::Write` sink that accumulates formatted text straight into a
+/// secured buffer, for assembling a secret out of pieces without ever
+/// materializing the combination in an unlocked `String` — the
+/// `fmt::Write` counterpart of the `io::Write` impl on `SecStr`. Usually
+/// used through [`sec_format!`]; the contents stay valid UTF-8 because
+/// `fmt::Write` only ever hands the sink `&str`.
+#[derive(Default)]
+pub struct SecWriter(SecStr);
+
+impl SecWriter {
+    pub fn new() -> SecWriter {
+        SecWriter(SecStr::new(Vec::new()))
+    }
+
+    /// Finish writing and hand over the accumulated secret; the same
+    /// locked buffer is reused, not copied.
+    pub fn into_sec_utf8(self) -> SecUtf8 {
+        SecUtf8(self.0)
+    }
+}
+
+impl fmt::Write for SecWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Like `format!`, but the formatted result lands directly in a
+/// [`SecUtf8`], never in an unlocked `String`:
+///
+/// ```
+/// # use secstr::{sec_format, SecUtf8};
+/// let prefix = SecUtf8::from("api");
+/// let token = sec_format!("{}:{}", prefix.unsecure(), 42);
+/// assert_eq!(token, SecUtf8::from("api:42"));
+/// ```
+///
+/// Note that any formatted *arguments* are produced by their own `Display`
+/// impls before reaching the secured sink; pass secret pieces as
+/// `.unsecure()` borrows of already-secured values, as above, to keep
+/// every copy protected.
+#[macro_export]
+macro_rules! sec_format {
+    ($($arg:tt)*) => {{
+        use std::fmt::Write;
+        let mut writer = $crate::SecWriter::new();
+        // writing into a secured buffer cannot fail
+        let _ = write!(writer, $($arg)*);
+        writer.into_sec_utf8()
+    }};
+}
+
+/// A secured platform string, for secrets `SecUtf8` can't represent: OS
+/// strings with non-UTF-8 bytes, or sensitive file paths. Wraps the
+/// platform-encoded units (bytes on unix, UTF-16 code units on Windows)
+/// in a `SecVec`, with the usual locking, zeroing-on-drop, constant-time
+/// comparison and redacted `Debug`/`Display`.
+#[cfg(any(unix, windows))]
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecOsString {
+    #[cfg(unix)]
+    content: SecVec<u8>,
+    #[cfg(windows)]
+    content: SecVec<u16>,
+}
+
+#[cfg(any(unix, windows))]
+impl SecOsString {
+    /// Secure the given `OsString`. On unix the backing byte buffer is
+    /// reused (not copied); on Windows the wide units are copied out,
+    /// since `OsString` doesn't expose its buffer (the source's copy is
+    /// out of this crate's hands, like `SecStr::from` on a borrow).
+    pub fn new(s: std::ffi::OsString) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStringExt;
+            SecOsString { content: SecVec::new(s.into_vec()) }
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStrExt;
+            SecOsString { content: SecVec::new(s.encode_wide().collect()) }
+        }
+    }
+
+    /// Borrow the contents as an `OsStr`.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn unsecure(&self) -> &std::ffi::OsStr {
+        use std::os::unix::ffi::OsStrExt;
+        std::ffi::OsStr::from_bytes(self.content.unsecure())
+    }
+
+    /// Borrow the raw UTF-16 code units. (A borrowed `&OsStr` view can't
+    /// be offered on Windows: `OsStr` doesn't wrap plain wide units.)
+    #[cfg(windows)]
+    #[must_use]
+    pub fn unsecure_wide(&self) -> &[u16] {
+        self.content.unsecure()
+    }
+
+    /// Turn the string into a regular `OsString` again, unprotected —
+    /// mirrors `SecUtf8::into_unsecure`. On unix the same buffer is
+    /// unlocked and handed over; on Windows the units are copied out and
+    /// the secured copy is wiped.
+    pub fn into_os_string(self) -> std::ffi::OsString {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStringExt;
+            std::ffi::OsString::from_vec(self.content.into_unsecure())
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStringExt;
+            std::ffi::OsString::from_wide(self.content.unsecure())
+            // `self` drops here, wiping the secured copy
+        }
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl From<std::ffi::OsString> for SecOsString {
+    fn from(s: std::ffi::OsString) -> SecOsString {
+        SecOsString::new(s)
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl From<&std::ffi::OsStr> for SecOsString {
+    fn from(s: &std::ffi::OsStr) -> SecOsString {
+        SecOsString::new(s.to_os_string())
+    }
+}
+
+// Make sure sensitive information is not logged accidentally
+#[cfg(any(unix, windows))]
+impl fmt::Debug for SecOsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+#[cfg(any(unix, windows))]
+impl fmt::Display for SecOsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+/// A data type suitable for storing sensitive information such as passwords and private keys in memory, that implements:
+///
+/// - Automatic zeroing in `Drop`
+/// - Constant time comparison in `PartialEq` (does not short circuit on the first different character; but terminates instantly if strings have different length)
+/// - Outputting `***SECRET***` to prevent leaking secrets into logs with `fmt::Debug` and `fmt::Display`
+/// - Automatic `mlock` to protect against leaking into swap (any unix)
+/// - Automatic `madvise(MADV_NOCORE/MADV_DONTDUMP)` to protect against leaking into core dumps (FreeBSD, DragonFlyBSD, Linux)
+///
+/// Comparisons using the `PartialEq` implementation are undefined behavior
+/// (and most likely wrong) if `T` has any padding bytes.
+pub struct SecBox<T>
+where
+    T: Sized + Copy,
+{
+    content: Box<T>,
+}
+
+impl<T> SecBox<T>
+where
+    T: Sized + Copy,
+{
+    pub fn new(cont: Box<T>) -> Self {
+        memlock::mlock(&*cont, 1);
+        SecBox { content: cont }
+    }
+
+    /// Borrow the contents of the `SecBox`.
+    #[must_use]
+    pub fn unsecure(&self) -> &T {
+        &self.content
+    }
+
+    /// Mutably borrow the contents of the `SecBox`.
+    #[must_use]
+    pub fn unsecure_mut(&mut self) -> &mut T {
+        &mut self.content
+    }
+
+    /// Turn the secret back into a regular `Box`, unprotected: the
+    /// allocation is unlocked and handed over as-is (not copied, not
+    /// zeroed), and the destructor does not run. Mirrors
+    /// `SecVec::into_unsecure` and `SecUtf8::into_unsecure`, so all three
+    /// types share the same auditable exit point. From the moment it
+    /// returns, nothing protects the contents anymore — they can reach
+    /// swap and core dumps, and zeroing the value after use is entirely on
+    /// the recipient.
+    #[must_use]
+    pub fn into_unsecure(self) -> Box<T> {
+        // unlock once (a no-op for ZSTs, matching `Drop`) and suppress the
+        // destructor so the handed-over box is neither wiped nor unlocked
+        // a second time
+        memlock::munlock(&*self.content, 1);
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never touched again and its `Drop` never runs,
+        // so the box has exactly one owner from here on.
+        unsafe { std::ptr::read(&this.content) }
+    }
+}
+
+impl<T> SecBox<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    /// Compare against a borrowed plain `T` in constant time, without
+    /// wrapping it in a `SecBox` first — no heap allocation or locking on
+    /// the comparison hot path; the operand can live on the stack or
+    /// inline in a message buffer. Same byte-representation semantics and
+    /// no-padding requirement as the `PartialEq` impl.
+    #[must_use]
+    pub fn ct_eq_value(&self, other: &T) -> bool {
+        // SAFETY: both references point to one initialized `T` and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        unsafe { mem::cmp(&*self.content, other, 1) }
+    }
+}
+
+impl<T, const N: usize> SecBox<[T; N]>
+where
+    T: Sized + Copy,
+{
+    /// Borrow the secured array as a slice, for handing a fixed-size key
+    /// to functions expecting `&[T]`. The array analogue of
+    /// `SecVec::unsecure`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.content[..]
+    }
+
+    /// Mutably borrow the secured array as a slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.content[..]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for SecBox<[T; N]>
+where
+    T: Sized + Copy,
+{
+    /// Secure a fixed-size array created inline, without going through
+    /// `Box::new(array)` first: the allocation is locked *before* the
+    /// array is copied into it, and the stack copy this function received
+    /// is wiped before returning, so the bytes never sit in an unlocked
+    /// heap allocation. (Copies the compiler made while the array was
+    /// being built are out of this crate's hands, as with `SecStr::from`.)
+    fn from(mut array: [T; N]) -> Self {
+        let mut uninit = Box::<[T; N]>::new_uninit();
+        memlock::mlock(uninit.as_ptr(), 1);
+        // SAFETY: the write fully initializes the freshly allocated box
+        let content = unsafe {
+            uninit.as_mut_ptr().write(array);
+            uninit.assume_init()
+        };
+        // SAFETY: `array` is this function's own copy, fully initialized,
+        // and is not read again after the wipe
+        unsafe { mem::zero(array.as_mut_ptr(), N) };
+        SecBox { content }
+    }
+}
+
+/// Overwrite the contents of a `SecBox` with zeros. This is automatically
+/// called in the destructor.
+///
+/// # Safety
+///
+/// An all-zero byte pattern must be a valid `T`; otherwise any further use
+/// of the boxed value is undefined behavior.
+pub unsafe fn zero_out_secbox<T: Sized + Copy>(secbox: &mut SecBox<T>) {
+    #[cfg(feature = "random-wipe")]
+    mem::random_overwrite(&mut *secbox.content as *mut T, 1);
+    mem::zero(&mut *secbox.content, 1);
+}
+
+impl<T> SecBox<T>
+where
+    T: Sized + Copy + ZeroValid,
+{
+    /// Overwrite the contents with zeros, safely: the
+    /// all-zero-is-valid precondition of
+    /// [`zero_out_secbox`](fn.zero_out_secbox.html) is proven by the
+    /// sealed [`ZeroValid`] bound, which covers the common key types
+    /// (primitives and fixed-size arrays of them). The unsafe free
+    /// function stays available for exotic `T`; with the `bytemuck`
+    /// feature, [`zero_out`](#method.zero_out) accepts anything
+    /// `Zeroable` instead.
+    pub fn wipe(&mut self) {
+        // SAFETY: `T: ZeroValid` guarantees the all-zero byte pattern is
+        // a valid `T`.
+        unsafe { zero_out_secbox(self) }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<T> SecBox<T>
+where
+    T: Sized + Copy + bytemuck::Zeroable,
+{
+    /// Overwrite the contents with zeros, like
+    /// [`zero_out_secbox`](fn.zero_out_secbox.html), but safe: the
+    /// all-zero-is-valid precondition is proven by the `Zeroable` bound
+    /// instead of asserted by the caller. (Also automatically called in
+    /// the destructor.)
+    pub fn zero_out(&mut self) {
+        // SAFETY: `T: Zeroable` guarantees the all-zero byte pattern is a
+        // valid `T`.
+        unsafe { zero_out_secbox(self) }
+    }
+}
+
+// Cloning
+impl<T> Clone for SecBox<T>
+where
+    T: Sized + Copy,
+{
+    fn clone(&self) -> Self {
+        // lock the new allocation *before* copying the secret into it, so
+        // the copy never sits in unlocked memory
+        let mut uninit = Box::<T>::new_uninit();
+        memlock::mlock(uninit.as_ptr(), 1);
+        // SAFETY: the write fully initializes the freshly allocated box
+        let content = unsafe {
+            uninit.as_mut_ptr().write(*self.content);
+            uninit.assume_init()
+        };
+        SecBox { content }
+    }
+}
+
+// Indexing
+impl<T, U> std::ops::Index<U> for SecBox<T>
+where
+    T: Sized + Copy + std::ops::Index<U>,
+{
+    type Output = <T as std::ops::Index<U>>::Output;
+
+    fn index(&self, index: U) -> &Self::Output {
+        std::ops::Index::index(&*self.content, index)
+    }
+}
+
+// Comparisons
+impl<T> PartialEq for SecBox<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    /// Always compares exactly `size_of::<T>()` bytes with no early exit:
+    /// unlike the `SecVec` comparisons, there is no length check at all —
+    /// the size is fixed by the type — so neither the timing nor the
+    /// result order reveals *which* byte differed. Verified by the
+    /// `timing-tests` suite.
+    fn eq(&self, other: &SecBox<T>) -> bool {
+        // SAFETY: both pointers refer to one initialized `T` and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        unsafe { mem::cmp(&*self.content, &*other.content, 1) }
+    }
+}
+
+impl<T> Eq for SecBox<T> where T: Sized + Copy + NoPaddingBytes {}
+
+// Cross-representation comparisons: one code path holds a key as a
+// fixed-size `SecBox<[u8; N]>`, another as a variable `SecStr` — compare
+// them byte-wise in constant time without exposing either side or copying
+// one into the other's representation. The length check against `N` is
+// immediate, like every other length comparison here (lengths are public).
+impl<const N: usize> PartialEq<SecVec<u8>> for SecBox<[u8; N]> {
+    fn eq(&self, other: &SecVec<u8>) -> bool {
+        if other.content.len() != N {
+            return false;
+        }
+        // SAFETY: both sides hold `N` initialized bytes.
+        unsafe { mem::cmp(self.content.as_ptr(), other.content.as_ptr(), N) }
+    }
+}
+
+impl<const N: usize> PartialEq<SecBox<[u8; N]>> for SecVec<u8> {
+    fn eq(&self, other: &SecBox<[u8; N]>) -> bool {
+        *other == *self
+    }
+}
+
+// Hashing — see the `SecVec` impls for the with/without-libsodium tradeoff
+// and for why `NoPaddingBytes` is required, not just a convention
+#[cfg(not(feature = "libsodium-sys"))]
+impl<T> std::hash::Hash for SecBox<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // SAFETY: the box holds one initialized `T` with no padding bytes.
+        state.write(unsafe {
+            std::slice::from_raw_parts(&*self.content as *const T as *const u8, std::mem::size_of::<T>())
+        });
+    }
+}
+
+#[cfg(feature = "libsodium-sys")]
+impl<T> std::hash::Hash for SecBox<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let mut hashed = [0u8; libsodium_sys::crypto_generichash_BYTES as usize];
+        unsafe {
+            libsodium_sys::crypto_generichash(
+                hashed.as_mut_ptr(),
+                hashed.len(),
+                &*self.content as *const T as *const u8,
+                std::mem::size_of::<T>() as u64,
+                std::ptr::null(),
+                0,
+            );
+        }
+        state.write(&hashed);
+    }
+}
+
+// Make sure sensitive information is not logged accidentally
+impl<T> fmt::Debug for SecBox<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl<T> fmt::Display for SecBox<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+// Delete sensitive information from memory
+impl<T> Drop for SecBox<T>
+where
+    T: Sized + Copy,
+{
+    fn drop(&mut self) {
+        // nothing to wipe or unlock for a ZST — and none was locked:
+        // `mlock`/`munlock` early-return on zero bytes, so skipping here
+        // keeps the pairing balanced. The box itself (a dangling
+        // well-aligned pointer, never handed to the allocator) is freed by
+        // `Box`'s own ZST-aware drop.
+        if std::mem::size_of::<T>() == 0 {
+            return;
+        }
+        // SAFETY: the value is never read as a `T` again; `T: Copy` means
+        // there is no drop glue that could observe the zeroed bytes.
+        unsafe { zero_out_secbox(self) };
+        #[cfg(feature = "verify-zero")]
+        unsafe {
+            mem::verify_zeroed(&*self.content, 1);
+        }
+        memlock::munlock(&*self.content, 1);
+    }
+}
+
+/// A data type suitable for storing sensitive information such as passwords and private keys in memory, that implements:
+///
+/// - Automatic zeroing in `Drop`
+/// - Constant time comparison in `PartialEq` (does not short circuit on the first different character; but terminates instantly if strings have different length)
+/// - Outputting `***SECRET***` to prevent leaking secrets into logs with `fmt::Debug` and `fmt::Display`
+/// - Automatic `mlock` to protect against leaking into swap (any unix)
+/// - Automatic `madvise(MADV_NOCORE/MADV_DONTDUMP)` to protect against leaking into core dumps (FreeBSD, DragonFlyBSD, Linux)
+///
+/// Like `SecBox`, but for runtime-sized `Box<[T]>` contents (e.g. keys
+/// whose length is only known at runtime).
+pub struct SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    content: Box<[T]>,
+}
+
+impl<T> SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    pub fn new(cont: Box<[T]>) -> Self {
+        memlock::mlock(cont.as_ptr(), cont.len());
+        SecBoxedSlice { content: cont }
+    }
+
+    /// Borrow the contents of the slice.
+    #[must_use]
+    pub fn unsecure(&self) -> &[T] {
+        &self.content
+    }
+
+    /// Mutably borrow the contents of the slice.
+    #[must_use]
+    pub fn unsecure_mut(&mut self) -> &mut [T] {
+        &mut self.content
+    }
+
+    /// Overwrite the slice with zeros. This is automatically called in the destructor.
+    pub fn zero_out(&mut self) {
+        // SAFETY: the slice contains `len` initialized elements and
+        // `T: Copy` means the zeroed bytes are never observed by drop glue.
+        unsafe {
+            #[cfg(feature = "random-wipe")]
+            mem::random_overwrite(self.content.as_mut_ptr(), self.content.len());
+            mem::zero(self.content.as_mut_ptr(), self.content.len());
+        }
+    }
+
+    /// Move the contents back into a [`SecVec`], the inverse of
+    /// [`SecVec::into_boxed_slice`]: the same buffer is reused with no
+    /// copy (a boxed slice is always tight, so `into_vec` does not
+    /// reallocate) and the lock/zero guarantees carry over seamlessly.
+    ///
+    /// [`SecVec`]: struct.SecVec.html
+    /// [`SecVec::into_boxed_slice`]: struct.SecVec.html#method.into_boxed_slice
+    pub fn into_sec_vec(mut self) -> SecVec<T> {
+        // mirror of `SecVec::take_content`: unlock once, move the buffer
+        // out, and skip `Drop` so it is neither wiped nor unlocked again
+        memlock::munlock(self.content.as_ptr(), self.content.len());
+        let content = std::mem::take(&mut self.content);
+        std::mem::forget(self);
+        SecVec::new(content.into_vec())
+    }
+}
+
+// Cloning
+impl<T> Clone for SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    fn clone(&self) -> Self {
+        // lock the new buffer *before* copying the secret into it, so the
+        // copy never sits in unlocked memory
+        let mut content = Vec::with_capacity(self.content.len());
+        memlock::mlock(content.as_ptr(), content.capacity());
+        content.extend_from_slice(&self.content);
+        // len == capacity here, so no reallocation happens
+        SecBoxedSlice {
+            content: content.into_boxed_slice(),
+        }
+    }
+}
+
+// Comparisons
+impl<T> PartialEq for SecBoxedSlice<T>
+where
+    T: Sized + Copy + NoPaddingBytes,
+{
+    fn eq(&self, other: &SecBoxedSlice<T>) -> bool {
+        let len = self.content.len();
+        if len != other.content.len() {
+            return false;
+        }
+        // SAFETY: both slices contain `len` initialized elements and
+        // `T: NoPaddingBytes` guarantees a padding-free representation.
+        unsafe { mem::cmp(self.content.as_ptr(), other.content.as_ptr(), len) }
+    }
+}
+
+impl<T> Eq for SecBoxedSlice<T> where T: Sized + Copy + NoPaddingBytes {}
+
+// Make sure sensitive information is not logged accidentally
+impl<T> fmt::Debug for SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl<T> fmt::Display for SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+// Delete sensitive information from memory
+impl<T> Drop for SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    fn drop(&mut self) {
+        self.zero_out();
+        // SAFETY: `zero_out` left all `len` elements zero-initialized
+        #[cfg(feature = "verify-zero")]
+        unsafe {
+            mem::verify_zeroed(self.content.as_ptr(), self.content.len());
+        }
+        memlock::munlock(self.content.as_ptr(), self.content.len());
+    }
+}
+
+/// Secured containers that can be wiped in place. The common vocabulary
+/// between the `Copy`-bound primitives (`SecVec`, `SecBox`, …), which all
+/// already expose an inherent `zero_out`, and [`SecList`], which composes
+/// them.
+pub trait ZeroOut {
+    /// Overwrite the contained secret with zeros, in a way the optimizer
+    /// cannot elide. (Also done automatically on drop.)
+    fn zero_out(&mut self);
+}
+
+impl<T> ZeroOut for SecVec<T>
+where
+    T: Sized + Copy,
+{
+    fn zero_out(&mut self) {
+        SecVec::zero_out(self)
+    }
+}
+
+impl ZeroOut for SecUtf8 {
+    fn zero_out(&mut self) {
+        self.0.zero_out()
+    }
+}
+
+impl<T> ZeroOut for SecBoxedSlice<T>
+where
+    T: Sized + Copy,
+{
+    fn zero_out(&mut self) {
+        SecBoxedSlice::zero_out(self)
+    }
+}
+
+// for `SecBox` the all-zero-is-valid precondition needs the `Zeroable`
+// proof, same as its inherent safe `zero_out`
+#[cfg(feature = "bytemuck")]
+impl<T> ZeroOut for SecBox<T>
+where
+    T: Sized + Copy + bytemuck::Zeroable,
+{
+    fn zero_out(&mut self) {
+        SecBox::zero_out(self)
+    }
+}
+
+/// An owning list of secured values, for nesting secrets without the
+/// `Copy` bound the flat containers require: `SecList<SecStr>` holds any
+/// number of independently-secured byte strings, each in its own locked
+/// buffer.
+///
+/// The list's spine (the `Vec` of handles) is ordinary unlocked memory —
+/// it holds only pointers and lengths, never secret bytes; all secret
+/// material lives inside the elements, which wipe themselves on drop as
+/// usual. What the list adds is one owner to drop (or [`zero_out`]
+/// eagerly, via the [`ZeroOut`] impl) and a redacted `Debug`, so a
+/// collection of secrets can be passed around as a unit without exposure.
+///
+/// This is deliberately a composition of the existing secured types, not
+/// a relaxation of their `Copy` bounds: element types with custom `Drop`
+/// glue remain out of scope for the flat containers.
+///
+/// [`zero_out`]: trait.ZeroOut.html#tymethod.zero_out
+#[derive(Default)]
+pub struct SecList<S>
+where
+    S: ZeroOut,
+{
+    items: Vec<S>,
+}
+
+impl<S> SecList<S>
+where
+    S: ZeroOut,
+{
+    pub fn new() -> Self {
+        SecList { items: Vec::new() }
+    }
+
+    /// Append a secured value, taking ownership of it.
+    pub fn push(&mut self, item: S) {
+        self.items.push(item);
+    }
+
+    /// Remove and return the last element; it stays secured, ownership
+    /// just moves to the caller.
+    pub fn pop(&mut self) -> Option<S> {
+        self.items.pop()
+    }
+
+    /// Remove and return the element at `index`, shifting the handles
+    /// (not the secrets) after it. Panics if `index` is out of bounds,
+    /// like `Vec::remove`.
+    pub fn remove(&mut self, index: usize) -> S {
+        self.items.remove(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&S> {
+        self.items.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut S> {
+        self.items.get_mut(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &S> {
+        self.items.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut S> {
+        self.items.iter_mut()
+    }
+
+    /// Drop all elements; each wipes itself on the way out.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+impl<S> ZeroOut for SecList<S>
+where
+    S: ZeroOut,
+{
+    /// Wipe every element in place, now, without waiting for drop order.
+    fn zero_out(&mut self) {
+        for item in self.items.iter_mut() {
+            item.zero_out();
+        }
+    }
+}
+
+impl<S> Extend<S> for SecList<S>
+where
+    S: ZeroOut,
+{
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+// Make sure sensitive information is not logged accidentally
+impl<S> fmt::Debug for SecList<S>
+where
+    S: ZeroOut,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} secrets]", self.items.len())
+    }
+}
+
+/// A scope guard over a *borrowed* `&mut [u8]` that volatile-zeroes it on
+/// drop — for memory this crate doesn't own, like a caller-provided
+/// decrypt-in-place buffer that must not keep the plaintext once the
+/// scope ends. Unlike the owning types it does not lock, allocate or take
+/// ownership: the crate's wipe primitive, and nothing else, applied to
+/// someone else's buffer. Access the bytes through `Deref`/`DerefMut`.
+///
+/// ```
+/// # use secstr::SecGuard;
+/// let mut scratch = *b"ciphertext"; // decrypted in place below
+/// {
+///     let mut guard = SecGuard::new(&mut scratch);
+///     guard[0] ^= 0x20; // work on the plaintext
+/// }
+/// assert_eq!(scratch, [0u8; 10]); // wiped at end of scope
+/// ```
+pub struct SecGuard<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> SecGuard<'a> {
+    pub fn new(buf: &'a mut [u8]) -> SecGuard<'a> {
+        SecGuard { buf }
+    }
+}
+
+impl std::ops::Deref for SecGuard<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+impl std::ops::DerefMut for SecGuard<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.buf
+    }
+}
+
+// Make sure sensitive information is not logged accidentally
+impl fmt::Debug for SecGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl fmt::Display for SecGuard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+// Delete sensitive information from memory (the borrowed buffer outlives
+// the guard — zeroed, not freed or unlocked)
+impl Drop for SecGuard<'_> {
+    fn drop(&mut self) {
+        // SAFETY: the borrowed slice is initialized for its whole length
+        // and zeroed bytes are valid `u8`s.
+        unsafe { mem::zero(self.buf.as_mut_ptr(), self.buf.len()) };
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic() {
+        let my_sec = SecStr::from("hello");
+        assert_eq!(my_sec, SecStr::from("hello".to_string()));
+        assert_eq!(my_sec.unsecure(), b"hello");
+    }
+
+    #[test]
+    fn test_zero_out() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.zero_out();
+        assert_eq!(my_sec.unsecure(), b"");
+    }
+
+    #[test]
+    fn test_zero_out_full_capacity() {
+        let mut my_sec = SecStr::from("hello");
+        let cap = my_sec.capacity();
+        my_sec.zero_out();
+        // look at the wiped capacity through the raw buffer
+        unsafe { my_sec.content.set_len(cap) };
+        assert!(my_sec.unsecure().iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_zero_out_under_unwind() {
+        let mut my_sec = SecStr::from("hello");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            my_sec.zero_out();
+            panic!("interrupted right after the wipe");
+        }));
+        assert!(result.is_err());
+        // the wipe must have happened even though the operation panicked
+        unsafe { my_sec.content.set_len(5) };
+        assert_eq!(my_sec.unsecure(), b"\x00\x00\x00\x00\x00");
+    }
+
+    #[test]
+    fn test_new_unlocked() {
+        let mut my_sec = SecStr::new_unlocked(b"hello".to_vec());
+        assert!(!my_sec.is_locked());
+        assert_eq!(my_sec.unsecure(), b"hello");
+        assert_eq!(my_sec, SecStr::from("hello"));
+        // growth keeps the instance out of the locking paths
+        my_sec.extend_from_slice(b" world");
+        assert!(!my_sec.is_locked());
+        assert_eq!(my_sec.unsecure(), b"hello world");
+        // the wipe still happens
+        my_sec.zero_out();
+        let cap = my_sec.capacity();
+        unsafe { my_sec.content.set_len(cap) };
+        assert!(my_sec.unsecure().iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_try_unsecure() {
+        let mut my_sec = SecStr::from("hello");
+        assert_eq!(my_sec.try_unsecure(), Ok(&b"hello"[..]));
+        my_sec.zero_out();
+        assert_eq!(my_sec.try_unsecure(), Err(Wiped));
+        // legitimately empty is not wiped
+        assert_eq!(SecStr::from("").try_unsecure(), Ok(&b""[..]));
+        // repopulating makes the secret live again
+        my_sec.extend_from_slice(b"new");
+        assert_eq!(my_sec.try_unsecure(), Ok(&b"new"[..]));
+        my_sec.zero_out();
+        my_sec.push(b'x');
+        assert_eq!(my_sec.try_unsecure(), Ok(&b"x"[..]));
+    }
+
+    #[test]
+    fn test_sec_guard() {
+        let mut scratch = *b"hunter2";
+        {
+            let mut guard = SecGuard::new(&mut scratch);
+            assert_eq!(&*guard, b"hunter2");
+            guard[0] = b'H';
+            assert_eq!(&guard[..2], b"Hu");
+            assert_eq!(format!("{:?}", guard), "***SECRET***");
+        }
+        // the borrowed buffer was wiped when the guard went out of scope
+        assert_eq!(scratch, [0u8; 7]);
+    }
+
+    /// Compile-time thread-safety contract: every secured container is
+    /// `Send + Sync` for appropriate contents, like the plain container it
+    /// wraps. If some future internal state (lock tracking, auditing)
+    /// reaches for a non-thread-safe cell, this stops compiling.
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SecStr>();
+        assert_send_sync::<SecVec<u64>>();
+        assert_send_sync::<SecUtf8>();
+        assert_send_sync::<SecBox<[u8; 32]>>();
+        assert_send_sync::<SecBoxedSlice<u8>>();
+        assert_send_sync::<SecOsString>();
+        assert_send_sync::<SecList<SecStr>>();
+        assert_send_sync::<SecPool>();
+        assert_send_sync::<PooledSec<'static>>();
+        assert_send_sync::<Wiped>();
+    }
+
+    #[test]
+    fn test_comparison() {
+        assert_eq!(SecStr::from("hello"), SecStr::from("hello"));
+        assert_ne!(SecStr::from("hello"), SecStr::from("yolo"));
+        assert_ne!(SecStr::from("hello"), SecStr::from("olleh"));
+        assert_ne!(SecStr::from("hello"), SecStr::from("helloworld"));
+        assert_ne!(SecStr::from("hello"), SecStr::from(""));
+    }
+
+    #[test]
+    fn test_comparison_odd_array_lengths() {
+        // `NoPaddingBytes` covers every array length via const generics,
+        // not just the historical hardcoded list — and nests
+        let my_sec: SecVec<[u8; 48]> = SecVec::new(vec![[7u8; 48]; 3]);
+        assert_eq!(my_sec, SecVec::new(vec![[7u8; 48]; 3]));
+        assert_ne!(my_sec, SecVec::new(vec![[8u8; 48]; 3]));
+        let nested = SecBox::new(Box::new([[1u16; 3]; 7]));
+        assert_eq!(nested, SecBox::new(Box::new([[1u16; 3]; 7])));
+        assert_ne!(nested, SecBox::new(Box::new([[2u16; 3]; 7])));
+    }
+
+    #[test]
+    fn test_comparison_ignores_capacity() {
+        // only `len` elements take part in equality: the same contents in
+        // buffers of wildly different capacities must compare equal
+        let a = SecStr::from("hello");
+        let mut b = SecStr::from("hello, but longer for a while");
+        b.resize(5, 0);
+        b.reserve_exact(4096);
+        assert!(b.capacity() != a.capacity());
+        assert_eq!(a, b);
+        let mut c = SecStr::from("hello");
+        c.shrink_to_fit();
+        assert_eq!(a, c);
+    }
+
+    #[test]
+    fn test_from_str_zeroing() {
+        let mut source = "hello".to_string();
+        let my_sec = SecStr::from_str_zeroing(&mut source);
+        assert_eq!(my_sec.unsecure(), b"hello");
+        assert_eq!(source, "");
+        // the source's buffer must have been wiped
+        unsafe {
+            let v = source.as_mut_vec();
+            let cap = v.capacity();
+            v.set_len(cap);
+            assert!(v.iter().all(|b| *b == 0));
+        }
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"hello", b"hello"));
+        assert!(!constant_time_eq(b"hello", b"yolo!"));
+        assert!(!constant_time_eq(b"hello", b"hello!"));
+        assert!(constant_time_eq(b"", b""));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn test_debug_common_prefix_len() {
+        let my_sec = SecStr::from("hunter2");
+        assert_eq!(my_sec.debug_common_prefix_len(&SecStr::from("hunter2")), 7);
+        assert_eq!(my_sec.debug_common_prefix_len(&SecStr::from("hunter3")), 6);
+        assert_eq!(my_sec.debug_common_prefix_len(&SecStr::from("hun")), 3);
+        assert_eq!(my_sec.debug_common_prefix_len(&SecStr::from("xunter2")), 0);
+        assert_eq!(my_sec.debug_common_prefix_len(&SecStr::from("")), 0);
+    }
+
+    #[test]
+    fn test_ct_starts_ends_with() {
+        let my_sec = SecStr::from("v1:key-material:tag");
+        assert!(my_sec.ct_starts_with(b"v1:"));
+        assert!(!my_sec.ct_starts_with(b"v2:"));
+        assert!(my_sec.ct_starts_with(b""));
+        assert!(!my_sec.ct_starts_with(b"v1:key-material:tag!"));
+        assert!(my_sec.ct_ends_with(b":tag"));
+        assert!(!my_sec.ct_ends_with(b":gat"));
+        assert!(my_sec.ct_ends_with(b""));
+        assert!(!SecStr::from("x").ct_ends_with(b"xx"));
+    }
+
+    #[test]
+    fn test_compare_digest() {
+        // both-bytes, both-str, and mixed views of the same bytes
+        assert!(compare_digest(b"hello", b"hello"));
+        assert!(compare_digest("hello", "hello"));
+        assert!(compare_digest("hello", b"hello"));
+        let owned: (String, Vec<u8>) = ("hello".into(), b"hello".to_vec());
+        assert!(compare_digest(owned.0, owned.1));
+        assert!(compare_digest([0xABu8; 32], [0xABu8; 32]));
+        assert!(!compare_digest("hello", "yolo!"));
+        assert!(!compare_digest("hello", "hello!"));
+    }
+
+    /// Dudect-style statistical check that the comparison's timing does
+    /// not depend on *where* (or whether) the inputs differ: measure many
+    /// batched comparisons of an equal pair and a differs-at-first-byte
+    /// pair in random interleaving, then require Welch's t-statistic
+    /// between the two timing distributions to stay below a threshold. A
+    /// data-dependent early exit (e.g. the optimizer rewriting the
+    /// accumulation into a memcmp) shows up as a huge |t| here long before
+    /// anyone notices it in a microbenchmark.
+    ///
+    /// Statistical and noisy by nature, hence behind `timing-tests`; the
+    /// threshold is deliberately lenient for shared CI machines.
+    #[cfg(feature = "timing-tests")]
+    #[test]
+    fn test_cmp_timing_indistinguishable() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        const LEN: usize = 4096;
+        const BATCH: usize = 64; // comparisons per timed sample
+        const SAMPLES: usize = 2000; // per class
+
+        let secret = SecStr::from(vec![0x5Au8; LEN]);
+        let equal = SecStr::from(vec![0x5Au8; LEN]);
+        let mut differing = SecStr::from(vec![0x5Au8; LEN]);
+        differing.unsecure_mut()[0] = 0xA5; // worst case for an early exit
+
+        // simple LCG for the interleaving order, to keep any drift (CPU
+        // frequency, cache warmth) from correlating with the class
+        let mut rng: u64 = 0x243F6A8885A308D3;
+        let mut times: [Vec<f64>; 2] = [Vec::with_capacity(SAMPLES), Vec::with_capacity(SAMPLES)];
+        while times[0].len() < SAMPLES || times[1].len() < SAMPLES {
+            rng = rng.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let class = ((rng >> 33) & 1) as usize;
+            if times[class].len() >= SAMPLES {
+                continue;
+            }
+            let other = if class == 0 { &equal } else { &differing };
+            let start = Instant::now();
+            for _ in 0..BATCH {
+                black_box(black_box(&secret) == black_box(other));
+            }
+            times[class].push(start.elapsed().as_nanos() as f64);
+        }
+
+        let t = welch_t(&mut times);
+        assert!(
+            t.abs() < 10.0,
+            "equal vs differing comparison timings are distinguishable: t = {:.2}",
+            t
+        );
+    }
+
+    /// Welch's t-statistic between two timing distributions, after
+    /// cropping the top decile of each (scheduler preemptions and
+    /// interrupts land there and only add variance).
+    #[cfg(feature = "timing-tests")]
+    fn welch_t(times: &mut [Vec<f64>; 2]) -> f64 {
+        for class in times.iter_mut() {
+            class.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let cropped = class.len() * 9 / 10;
+            class.truncate(cropped);
+        }
+        let stats = |xs: &[f64]| {
+            let n = xs.len() as f64;
+            let mean = xs.iter().sum::<f64>() / n;
+            let var = xs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+            (mean, var, n)
+        };
+        let (m0, v0, n0) = stats(&times[0]);
+        let (m1, v1, n1) = stats(&times[1]);
+        (m0 - m1) / (v0 / n0 + v1 / n1).sqrt()
+    }
+
+    /// `SecBox::eq` always walks all of `T`: the timing must not reveal
+    /// *which* byte differs, so compare differs-at-first-byte against
+    /// differs-at-last-byte.
+    #[cfg(feature = "timing-tests")]
+    #[test]
+    fn test_secbox_eq_timing_position_independent() {
+        use std::hint::black_box;
+        use std::time::Instant;
+
+        const BATCH: usize = 64;
+        const SAMPLES: usize = 2000;
+
+        let secret = SecBox::new(Box::new([0x5Au8; 1024]));
+        let mut first = [0x5Au8; 1024];
+        first[0] = 0xA5;
+        let first = SecBox::new(Box::new(first));
+        let mut last = [0x5Au8; 1024];
+        last[1023] = 0xA5;
+        let last = SecBox::new(Box::new(last));
+
+        let mut rng: u64 = 0x13198A2E03707344;
+        let mut times: [Vec<f64>; 2] = [Vec::with_capacity(SAMPLES), Vec::with_capacity(SAMPLES)];
+        while times[0].len() < SAMPLES || times[1].len() < SAMPLES {
+            rng = rng.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let class = ((rng >> 33) & 1) as usize;
+            if times[class].len() >= SAMPLES {
+                continue;
+            }
+            let other = if class == 0 { &first } else { &last };
+            let start = Instant::now();
+            for _ in 0..BATCH {
+                black_box(black_box(&secret) == black_box(other));
+            }
+            times[class].push(start.elapsed().as_nanos() as f64);
+        }
+
+        let t = welch_t(&mut times);
+        assert!(
+            t.abs() < 10.0,
+            "comparison timing depends on the differing byte's position: t = {:.2}",
+            t
+        );
+    }
+
+    #[test]
+    fn test_zero_all() {
+        let mut store: Vec<SecStr> = vec![
+            SecStr::from("hunter2"),
+            SecStr::from("correct horse"),
+            SecStr::from(""),
+        ];
+        let caps: Vec<usize> = store.iter().map(|s| s.capacity()).collect();
+        zero_all(&mut store);
+        for (sec, cap) in store.iter_mut().zip(caps) {
+            assert!(sec.unsecure().is_empty());
+            // the full former capacity must have been wiped
+            unsafe { sec.content.set_len(cap) };
+            assert!(sec.unsecure().iter().all(|b| *b == 0));
+            unsafe { sec.content.set_len(0) };
+        }
+    }
+
+    #[test]
+    fn test_ct_eq_bytes() {
+        let my_sec = SecStr::from("hello");
+        assert!(my_sec.ct_eq_bytes(b"hello".to_vec()));
+        assert!(my_sec.ct_eq_bytes(&b"hello"[..]));
+        assert!(my_sec.ct_eq_bytes([104u8, 101, 108, 108, 111]));
+        assert!(my_sec.ct_eq_bytes(SecStr::from("hello").unsecure()));
+        assert!(!my_sec.ct_eq_bytes(b"yello".to_vec()));
+        assert!(!my_sec.ct_eq_bytes(&b"hell"[..]));
+    }
+
+    #[test]
+    fn test_verify_mac() {
+        let expected = SecStr::from(vec![0xABu8; 32]);
+        assert!(verify_mac(&expected, &[0xAB; 32]));
+        assert!(!verify_mac(&expected, &[0xAC; 32]));
+        assert!(!verify_mac(&expected, &[0xAB; 16]));
+        #[cfg(feature = "subtle")]
+        {
+            assert_eq!(verify_mac_choice(&expected, &[0xAB; 32]).unwrap_u8(), 1);
+            assert_eq!(verify_mac_choice(&expected, &[0xAC; 32]).unwrap_u8(), 0);
+        }
+    }
+
+    #[test]
+    fn test_ct_cmp() {
+        use std::cmp::Ordering;
+        assert_eq!(SecStr::from("hello").ct_cmp(&SecStr::from("hello")), Ordering::Equal);
+        assert_eq!(SecStr::from("hella").ct_cmp(&SecStr::from("hello")), Ordering::Less);
+        assert_eq!(SecStr::from("hellp").ct_cmp(&SecStr::from("hello")), Ordering::Greater);
+        assert_eq!(SecStr::from("hell").ct_cmp(&SecStr::from("hello")), Ordering::Less);
+        assert_eq!(SecStr::from("hello!").ct_cmp(&SecStr::from("hello")), Ordering::Greater);
+        assert_eq!(SecStr::from("abc").ct_cmp(&SecStr::from("b")), Ordering::Less);
+        assert_eq!(SecUtf8::from("aä").ct_cmp(&SecUtf8::from("ab")), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_ct_eq_padded() {
+        assert!(SecStr::from("hello").ct_eq_padded(&SecStr::from("hello"), 64));
+        assert!(!SecStr::from("hello").ct_eq_padded(&SecStr::from("yolo!"), 64));
+        assert!(!SecStr::from("hello").ct_eq_padded(&SecStr::from("hell"), 64));
+        assert!(!SecStr::from("hello").ct_eq_padded(&SecStr::from(""), 64));
+        assert!(SecStr::from("hello").ct_eq_padded(&SecStr::from("hello"), 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ct_eq_padded_too_long() {
+        let _ = SecStr::from("hello").ct_eq_padded(&SecStr::from("hello"), 4);
+    }
+
+    #[test]
+    fn test_indexing() {
+        let string = SecStr::from("hello");
+        assert_eq!(string[0], b'h');
+        assert_eq!(&string[3..5], b"lo");
+    }
+
+    #[test]
+    fn test_indexing_mut() {
+        let mut string = SecStr::from("hello");
+        string[0] = b'y';
+        string[3..5].copy_from_slice(b"no");
+        assert_eq!(string.unsecure(), b"yelno");
+    }
+
+    #[test]
+    fn test_scoped_access() {
+        let mut my_sec = SecStr::from("hello");
+        assert_eq!(my_sec.with_unsecure(|s| s.len()), 5);
+        my_sec.with_unsecure_mut(|s| s.reverse());
+        assert_eq!(my_sec.unsecure(), b"olleh");
+    }
+
+    #[test]
+    fn test_ct_assign_if() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.ct_assign_if(false, b"waldo");
+        assert_eq!(my_sec.unsecure(), b"hello");
+        my_sec.ct_assign_if(true, b"waldo");
+        assert_eq!(my_sec.unsecure(), b"waldo");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ct_assign_if_length_mismatch() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.ct_assign_if(true, b"too long");
+    }
+
+    #[test]
+    fn test_show() {
+        assert_eq!(format!("{:?}", SecStr::from("hello")), "***SECRET***");
+        assert_eq!(format!("{}", SecStr::from("hello")), "***SECRET***");
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.resize(2, 0);
+        assert_eq!(my_sec.unsecure(), b"he");
+        my_sec.resize(8, b'x');
+        assert_eq!(my_sec.unsecure(), b"hexxxxxx");
+    }
+
+    #[test]
+    fn test_resize_shrink_zeroes_tail() {
+        let mut my_sec = SecStr::from("hunter2");
+        my_sec.resize(2, 0);
+        assert_eq!(my_sec.unsecure(), b"hu");
+        // the vacated region must not retain the secret
+        unsafe { my_sec.content.set_len(7) };
+        assert_eq!(my_sec.unsecure(), b"hu\x00\x00\x00\x00\x00");
+    }
+
+    #[test]
+    fn test_io_write() {
+        use std::io::Write;
+        let mut my_sec = SecStr::default();
+        let password = "hunter2".to_string();
+        write!(my_sec, "user:{}", password).unwrap();
+        my_sec.flush().unwrap();
+        assert_eq!(my_sec, SecStr::from("user:hunter2"));
+    }
+
+    #[test]
+    fn test_push_extend_from_slice() {
+        let mut my_sec = SecStr::from("he");
+        my_sec.push(b'l');
+        my_sec.extend_from_slice(b"lo");
+        assert_eq!(my_sec.unsecure(), b"hello");
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut my_sec = SecStr::from("hello");
+        let cap = my_sec.capacity();
+        my_sec.fill(b'x');
+        assert_eq!(my_sec.unsecure(), b"xxxxx");
+        assert_eq!(my_sec.capacity(), cap);
+    }
+
+    #[test]
+    fn test_map_in_place() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.map_in_place(|b| b.to_ascii_uppercase());
+        assert_eq!(my_sec.unsecure(), b"HELLO");
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut my_sec = SecStr::from("hllo");
+        my_sec.insert(1, b'e');
+        assert_eq!(my_sec.unsecure(), b"hello");
+        assert_eq!(my_sec.remove(4), b'o');
+        assert_eq!(my_sec.unsecure(), b"hell");
+        // the vacated slot must have been wiped
+        unsafe { my_sec.content.set_len(5) };
+        assert_eq!(my_sec.unsecure(), b"hell\x00");
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut my_sec = SecStr::from("hello");
+        let ptr = my_sec.unsecure().as_ptr();
+        for b in my_sec.iter_mut() {
+            *b ^= 0x20;
+        }
+        assert_eq!(my_sec.unsecure(), b"HELLO");
+        // `&mut` loop sugar, in place in the same buffer
+        for b in &mut my_sec {
+            *b ^= 0x20;
+        }
+        assert_eq!(my_sec.unsecure(), b"hello");
+        assert_eq!(my_sec.unsecure().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_splice() {
+        // same length: pure in-place overwrite
+        let mut my_sec = SecStr::from("user:xxxx:tail");
+        my_sec.splice(5..9, b"yyyy");
+        assert_eq!(my_sec.unsecure(), b"user:yyyy:tail");
+        // longer replacement grows through the secured path
+        my_sec.splice(5..9, b"long-field");
+        assert_eq!(my_sec.unsecure(), b"user:long-field:tail");
+        // shorter replacement shrinks and wipes the vacated tail
+        let old_len = my_sec.len();
+        my_sec.splice(5..15, b"z");
+        assert_eq!(my_sec.unsecure(), b"user:z:tail");
+        unsafe { my_sec.content.set_len(old_len) };
+        assert_eq!(&my_sec.unsecure()[11..], &[0u8; 9]);
+        // open-ended ranges, like Vec::splice
+        let mut my_sec = SecStr::from("hello");
+        my_sec.splice(..2, b"JE");
+        assert_eq!(my_sec.unsecure(), b"JEllo");
+        my_sec.splice(2.., b"");
+        assert_eq!(my_sec.unsecure(), b"JE");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_splice_out_of_bounds() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.splice(3..9, b"x");
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut my_sec = SecStr::from("h e l l o");
+        my_sec.retain(|b| *b != b' ');
+        assert_eq!(my_sec.unsecure(), b"hello");
+        // the vacated tail must have been wiped
+        unsafe { my_sec.content.set_len(9) };
+        assert_eq!(my_sec.unsecure(), b"hello\x00\x00\x00\x00");
+    }
+
+    #[test]
+    fn test_copy_from_slice() {
+        let mut my_sec = SecStr::from("hello");
+        let cap = my_sec.capacity();
+        my_sec.copy_from_slice(b"world");
+        assert_eq!(my_sec.unsecure(), b"world");
+        assert_eq!(my_sec.capacity(), cap);
+        // multi-byte elements
+        let mut my_sec: SecVec<u32> = SecVec::new(vec![1, 2, 3]);
+        my_sec.copy_from_slice(&[7, 8, 9]);
+        assert_eq!(my_sec.unsecure(), [7, 8, 9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_copy_from_slice_length_mismatch() {
+        SecStr::from("hello").copy_from_slice(b"hi");
+    }
+
+    #[test]
+    fn test_into_unsecure() {
+        let my_sec = SecStr::from("hello");
+        let ptr = my_sec.unsecure().as_ptr();
+        let raw = my_sec.into_unsecure();
+        assert_eq!(raw, b"hello");
+        // the same buffer is handed over, not a copy — and not zeroed
+        assert_eq!(raw.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_eq_against_literals() {
+        let my_sec = SecStr::from("hello");
+        assert_eq!(my_sec, b"hello"[..]);
+        assert_ne!(my_sec, b"yello"[..]);
+        assert_eq!(my_sec, &b"hello"[..]);
+        let my_sec = SecUtf8::from("hello");
+        assert_eq!(my_sec, *"hello");
+        assert_eq!(my_sec, "hello");
+        assert_ne!(my_sec, "hell");
+    }
+
+    #[test]
+    fn test_ffi_accessors() {
+        let mut my_sec = SecStr::from("hello");
+        assert_eq!(my_sec.len(), 5);
+        assert!(!my_sec.is_empty());
+        assert_eq!(my_sec.as_ptr(), my_sec.unsecure().as_ptr());
+        // simulate a C callee writing through the mutable pointer
+        unsafe { *my_sec.as_mut_ptr() = b'y' };
+        assert_eq!(my_sec.unsecure(), b"yello");
+    }
+
+    #[test]
+    fn test_try_reserve() {
+        let mut my_sec = SecStr::from("hello");
+        assert!(my_sec.try_reserve(1024).is_ok());
+        assert!(my_sec.capacity() >= 1029);
+        assert_eq!(my_sec.unsecure(), b"hello");
+        // an absurd request errors instead of aborting, leaving the
+        // contents untouched
+        assert!(my_sec.try_reserve(usize::MAX - 1).is_err());
+        assert_eq!(my_sec.unsecure(), b"hello");
+
+        let my_sec = SecStr::try_with_capacity(64).unwrap();
+        assert!(my_sec.capacity() >= 64);
+        assert!(my_sec.is_empty());
+        assert!(SecStr::try_with_capacity(usize::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn test_spare_capacity_fill_then_commit() {
+        let mut my_sec = SecStr::try_with_capacity(8).unwrap();
+        let ptr = my_sec.as_ptr();
+        for (i, slot) in my_sec.spare_capacity_mut().iter_mut().take(5).enumerate() {
+            slot.write(b'a' + i as u8);
+        }
+        // SAFETY: the first 5 bytes were just initialized above
+        unsafe { my_sec.set_len(5) };
+        assert_eq!(my_sec.unsecure(), b"abcde");
+        // filled in place, no reallocation
+        assert_eq!(my_sec.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_shrink_to_fit() {
+        let mut my_sec = SecStr::from("hunter2");
+        my_sec.reserve_exact(1024);
+        assert!(my_sec.capacity() >= 1031);
+        my_sec.shrink_to_fit();
+        assert_eq!(my_sec.capacity(), 7);
+        assert_eq!(my_sec.unsecure(), b"hunter2");
+        // already tight: a no-op
+        let ptr = my_sec.unsecure().as_ptr();
+        my_sec.shrink_to_fit();
+        assert_eq!(my_sec.unsecure().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut my_sec = SecStr::from("hel");
+        my_sec.extend(b"lo".iter());
+        assert_eq!(my_sec.unsecure(), b"hello");
+        my_sec.extend(b" world".iter().copied());
+        assert_eq!(my_sec.unsecure(), b"hello world");
+    }
+
+    #[test]
+    fn test_first_last_copy_range() {
+        let my_sec = SecStr::from("hello");
+        assert_eq!(my_sec.first(), Some(b'h'));
+        assert_eq!(my_sec.last(), Some(b'o'));
+        assert_eq!(SecStr::default().first(), None);
+        assert_eq!(SecStr::default().last(), None);
+        let prefix = my_sec.copy_range(..2);
+        assert_eq!(prefix.unsecure(), b"he");
+        assert_ne!(prefix.unsecure().as_ptr(), my_sec.unsecure().as_ptr());
+        assert_eq!(my_sec.copy_range(2..).unsecure(), b"llo");
+    }
+
+    #[test]
+    fn test_split_at() {
+        let keystream = SecStr::from("enckey--mackey--");
+        let (enc, mac) = keystream.split_at(8);
+        assert_eq!(enc.unsecure(), b"enckey--");
+        assert_eq!(mac.unsecure(), b"mackey--");
+        // independent owners, source untouched
+        assert_eq!(keystream.unsecure(), b"enckey--mackey--");
+        assert_ne!(enc.unsecure().as_ptr(), keystream.unsecure().as_ptr());
+        let (empty, all) = keystream.split_at(0);
+        assert!(empty.unsecure().is_empty());
+        assert_eq!(all.unsecure(), keystream.unsecure());
+    }
+
+    #[test]
+    fn test_chunks() {
+        let my_sec = SecStr::from("hello world");
+        let blocks: Vec<&[u8]> = my_sec.chunks(4).collect();
+        assert_eq!(blocks, [&b"hell"[..], b"o wo", b"rld"]);
+        // borrows into the locked buffer, not copies
+        assert_eq!(blocks[0].as_ptr(), my_sec.unsecure().as_ptr());
+    }
+
+    #[test]
+    fn test_as_chunks() {
+        let my_sec = SecStr::from("hello world");
+        let (blocks, rest) = my_sec.as_chunks::<4>();
+        assert_eq!(blocks, [*b"hell", *b"o wo"]);
+        assert_eq!(rest, b"rld");
+        // borrows into the locked buffer, not copies
+        assert_eq!(blocks.as_ptr() as *const u8, my_sec.unsecure().as_ptr());
+        // exact fit: empty remainder
+        let exact = SecStr::from("abcdefgh");
+        let (blocks, rest) = exact.as_chunks::<4>();
+        assert_eq!(blocks.len(), 2);
+        assert!(rest.is_empty());
+        // shorter than one block: everything is remainder
+        let short = SecStr::from("abc");
+        let (blocks, rest) = short.as_chunks::<4>();
+        assert!(blocks.is_empty());
+        assert_eq!(rest, b"abc");
+    }
+
+    #[test]
+    fn test_windows() {
+        let my_sec = SecStr::from("key:id");
+        assert_eq!(my_sec.windows(2).position(|w| w == b":i"), Some(3));
+        assert_eq!(my_sec.windows(6).count(), 1);
+        assert_eq!(my_sec.windows(7).count(), 0);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut my_sec = SecStr::from("hello");
+        let ptr = my_sec.unsecure().as_ptr();
+        my_sec.rotate_left(2);
+        assert_eq!(my_sec.unsecure(), b"llohe");
+        my_sec.rotate_right(2);
+        assert_eq!(my_sec.unsecure(), b"hello");
+        // in place, no reallocation
+        assert_eq!(my_sec.unsecure().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut odd = SecStr::from("hello");
+        let ptr = odd.unsecure().as_ptr();
+        odd.reverse();
+        assert_eq!(odd.unsecure(), b"olleh");
+        // in place, no reallocation
+        assert_eq!(odd.unsecure().as_ptr(), ptr);
+        let mut even = SecStr::from("abcd");
+        even.reverse();
+        assert_eq!(even.unsecure(), b"dcba");
+        let mut empty = SecStr::from("");
+        empty.reverse();
+        assert_eq!(empty.unsecure(), b"");
+    }
+
+    #[test]
+    fn test_explicit_clone() {
+        let my_sec = SecStr::from("hello");
+        let other = my_sec.explicit_clone();
+        assert_eq!(my_sec, other);
+        assert_ne!(my_sec.unsecure().as_ptr(), other.unsecure().as_ptr());
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_audit_label() {
+        // the events go to whatever tracing subscriber is installed; here
+        // just make sure labeling composes and survives cloning
+        let my_sec = SecStr::from("hunter2").with_label("db-password");
+        assert_eq!(my_sec.unsecure(), b"hunter2");
+        let other = my_sec.clone();
+        assert_eq!(other.label.as_deref(), Some("db-password"));
+    }
+
+    #[test]
+    fn test_is_locked() {
+        // zero-length buffers have nothing to lock, which counts as success
+        assert!(SecStr::new(Vec::new()).is_locked());
+        let mut my_sec = SecStr::from("hello");
+        #[cfg(unix)]
+        assert!(my_sec.is_locked());
+        #[cfg(not(unix))]
+        assert!(!my_sec.is_locked());
+        // the flag tracks the current buffer across reallocations, and a
+        // clone has its own lock on its own buffer
+        my_sec.reserve(4096);
+        let other = my_sec.clone();
+        #[cfg(unix)]
+        {
+            assert!(my_sec.is_locked());
+            assert!(other.is_locked());
+        }
+        #[cfg(not(unix))]
+        assert!(!other.is_locked());
+    }
+
+    #[test]
+    fn test_get_swap_remove() {
+        let mut my_sec = SecStr::from("hello");
+        assert_eq!(my_sec.get(1), Some(b'e'));
+        assert_eq!(my_sec.get(5), None);
+        assert_eq!(my_sec.swap_remove(0), b'h');
+        assert_eq!(my_sec.unsecure(), b"oell");
+        // the vacated slot must have been wiped
+        unsafe { my_sec.content.set_len(5) };
+        assert_eq!(my_sec.unsecure(), b"oell\x00");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_out_of_bounds() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.remove(5);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut my_sec = SecStr::from("hello ");
+        let mut other = SecStr::from("world");
+        let other_cap = other.capacity();
+        my_sec.append(&mut other);
+        assert_eq!(my_sec.unsecure(), b"hello world");
+        assert_eq!(other.unsecure(), b"");
+        // the source's buffer must have been wiped
+        unsafe { other.content.set_len(other_cap) };
+        assert!(other.unsecure().iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut my_sec = SecStr::from("hello");
+        my_sec.reserve(100);
+        assert!(my_sec.capacity() >= 105);
+        assert_eq!(my_sec.unsecure(), b"hello");
+        let mut my_sec = SecStr::from("hello");
+        my_sec.reserve_exact(3);
+        assert!(my_sec.capacity() >= 8);
+        assert_eq!(my_sec.unsecure(), b"hello");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_reserve_overflow() {
+        // matches `Vec::reserve`: panic, not a silent wrapping no-op
+        SecStr::from("hello").reserve(usize::MAX);
+    }
+
+    #[test]
+    fn test_multibyte() {
+        let my_sec = SecVec::from(vec![777u16, 0xFFFF]);
+        assert_eq!(my_sec, SecVec::from(vec![777u16, 0xFFFF]));
+        assert_ne!(my_sec, SecVec::from(vec![777u16, 0xFFFE]));
+    }
+
+    #[test]
+    fn test_ct_eq_elements() {
+        let my_sec = SecVec::from(vec![777u16, 0xFFFF]);
+        assert!(my_sec.ct_eq_elements(&SecVec::from(vec![777u16, 0xFFFF])));
+        assert!(!my_sec.ct_eq_elements(&SecVec::from(vec![777u16, 0xFFFE])));
+        assert!(!my_sec.ct_eq_elements(&SecVec::from(vec![777u16])));
+    }
+
+    #[test]
+    fn test_hashmap_key() {
+        use std::collections::HashMap;
+        let mut allowed: HashMap<SecStr, &str> = HashMap::new();
+        allowed.insert(SecStr::from("token-one"), "alice");
+        allowed.insert(SecStr::from("token-two"), "bob");
+        // lookups go through `Hash` + the constant-time `Eq`
+        assert_eq!(allowed.get(&SecStr::from("token-one")), Some(&"alice"));
+        assert_eq!(allowed.get(&SecStr::from("token-три")), None);
+    }
+
+    #[cfg(feature = "libsodium-sys")]
+    #[test]
+    fn test_hashing() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        let mut hasher_a = DefaultHasher::new();
+        SecStr::from("hello").hash(&mut hasher_a);
+        let mut hasher_b = DefaultHasher::new();
+        SecStr::from("hello").hash(&mut hasher_b);
+        assert_eq!(hasher_a.finish(), hasher_b.finish());
+    }
+
+    #[test]
+    fn test_utf8_hashing() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+        assert_eq!(hash_of(&SecUtf8::from("hello")), hash_of(&SecUtf8::from("hello")));
+        // parity with `SecStr`: the same bytes hash the same way
+        assert_eq!(hash_of(&SecUtf8::from("hello")), hash_of(&SecStr::from("hello")));
+        let mut names: std::collections::HashMap<SecUtf8, &str> = std::collections::HashMap::new();
+        names.insert(SecUtf8::from("token-one"), "alice");
+        assert_eq!(names.get(&SecUtf8::from("token-one")), Some(&"alice"));
+        assert_eq!(names.get(&SecUtf8::from("token-two")), None);
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn test_subtle_ct_eq() {
+        use subtle::ConstantTimeEq;
+        assert_eq!(SecStr::from("hello").ct_eq(&SecStr::from("hello")).unwrap_u8(), 1);
+        assert_eq!(SecStr::from("hello").ct_eq(&SecStr::from("yolo!")).unwrap_u8(), 0);
+        assert_eq!(SecStr::from("hello").ct_eq(&SecStr::from("longer")).unwrap_u8(), 0);
+    }
+
+    #[cfg(feature = "secrecy")]
+    #[test]
+    fn test_secrecy_interop() {
+        use secrecy::ExposeSecret;
+        let my_sec = SecStr::from("hello");
+        assert_eq!(my_sec.expose_secret(), b"hello");
+        let secret: secrecy::Secret<Vec<u8>> = my_sec.into();
+        assert_eq!(secret.expose_secret(), b"hello");
+        let back = SecStr::from_secret(&secret);
+        assert_eq!(back.unsecure(), b"hello");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialise_deserialise() {
+        let my_sec = SecStr::from("hello");
+        let buf = serde_cbor::to_vec(&my_sec).unwrap();
+        let my_sec2: SecStr = serde_cbor::from_slice(&buf).unwrap();
+        assert_eq!(my_sec, my_sec2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialise_byte_seq() {
+        // JSON has no bytes type, so the field arrives as an array of ints
+        let my_sec: SecStr = serde_json::from_str("[104, 101, 108, 108, 111]").unwrap();
+        assert_eq!(my_sec, SecStr::from("hello"));
+        let empty: SecStr = serde_json::from_str("[]").unwrap();
+        assert_eq!(empty, SecStr::from(""));
+        assert!(serde_json::from_str::<SecStr>("[256]").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialise_hostile_length() {
+        // a few bytes of CBOR can declare a 2^60-element array; the claimed
+        // length must not be preallocated wholesale, just rejected when the
+        // elements fail to materialize
+        let mut buf = vec![0x9b]; // array, 8-byte length follows
+        buf.extend_from_slice(&(1u64 << 60).to_be_bytes());
+        assert!(serde_cbor::from_slice::<SecStr>(&buf).is_err());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(SecStr::default(), SecStr::from(""));
+        assert_eq!(SecUtf8::default(), SecUtf8::from(""));
+    }
+
+    #[test]
+    fn test_utf8_basic() {
+        let my_sec = SecUtf8::from("hello");
+        assert_eq!(my_sec, SecUtf8::from("hello".to_string()));
+        assert_eq!(my_sec.unsecure(), "hello");
+    }
+
+    #[test]
+    fn test_utf8_from_sec_vec() {
+        let v = SecStr::from("hello");
+        let ptr = v.unsecure().as_ptr();
+        let my_sec = SecUtf8::from_sec_vec(v).unwrap();
+        assert_eq!(my_sec, SecUtf8::from("hello"));
+        // the same locked buffer is reused, not copied
+        assert_eq!(my_sec.unsecure().as_ptr(), ptr);
+        let (back, _err) = SecUtf8::from_sec_vec(SecStr::from(vec![0xffu8, 0xfe])).unwrap_err();
+        assert_eq!(back.unsecure(), &[0xffu8, 0xfe]);
+    }
+
+    #[test]
+    fn test_utf8_from_iterator_of_chars() {
+        let my_sec: SecUtf8 = "secret passphrase".chars().filter(|c| *c != ' ').collect();
+        assert_eq!(my_sec.unsecure(), "secretpassphrase");
+        // multi-byte chars are encoded correctly
+        let my_sec: SecUtf8 = "pässwörd€".chars().collect();
+        assert_eq!(my_sec.unsecure(), "pässwörd€");
+        let my_sec: SecUtf8 = std::iter::empty::<char>().collect();
+        assert_eq!(my_sec.unsecure(), "");
+    }
+
+    #[test]
+    fn test_utf8_ct_eq_ignore_ascii_case() {
+        assert!(SecUtf8::from("Hunter2").ct_eq_ignore_ascii_case(&SecUtf8::from("hUNTER2")));
+        assert!(!SecUtf8::from("hunter2").ct_eq_ignore_ascii_case(&SecUtf8::from("hunter3")));
+        assert!(!SecUtf8::from("hunter2").ct_eq_ignore_ascii_case(&SecUtf8::from("hunter22")));
+        // `{` and `[` differ only in the case bit — folding must not
+        // conflate non-letters
+        assert!(!SecUtf8::from("{").ct_eq_ignore_ascii_case(&SecUtf8::from("[")));
+        // non-ASCII is not folded
+        assert!(!SecUtf8::from("ä").ct_eq_ignore_ascii_case(&SecUtf8::from("Ä")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_os_string() {
+        use std::ffi::{OsStr, OsString};
+        use std::os::unix::ffi::OsStrExt;
+        // non-UTF-8 bytes that `SecUtf8` can't hold
+        let raw = OsStr::from_bytes(b"key-\xff\xfe").to_os_string();
+        let my_sec = SecOsString::new(raw.clone());
+        assert_eq!(my_sec.unsecure(), raw.as_os_str());
+        assert_eq!(my_sec, SecOsString::from(raw.as_os_str()));
+        assert_ne!(my_sec, SecOsString::from(OsString::from("key-")));
+        assert_eq!(format!("{:?}", my_sec), "***SECRET***");
+        assert_eq!(format!("{}", my_sec), "***SECRET***");
+        assert_eq!(my_sec.clone().into_os_string(), raw);
+    }
+
+    #[test]
+    fn test_sec_format() {
+        let prefix = SecUtf8::from("user");
+        let secret = SecStr::from("hunter2");
+        let combined = sec_format!(
+            "{}:{}",
+            prefix.unsecure(),
+            std::str::from_utf8(secret.unsecure()).unwrap()
+        );
+        assert_eq!(combined, SecUtf8::from("user:hunter2"));
+        assert_eq!(sec_format!("no args"), SecUtf8::from("no args"));
+    }
+
+    #[test]
+    fn test_utf8_consume_string() {
+        let s = String::from("hunter2");
+        let ptr = s.as_ptr();
+        let my_sec = SecUtf8::consume_string(s);
+        assert_eq!(my_sec.unsecure(), "hunter2");
+        // the same backing buffer is locked, not a copy
+        assert_eq!(my_sec.unsecure().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_utf8_from_env() {
+        std::env::set_var("SECSTR_TEST_FROM_ENV", "hunter2");
+        let my_sec = SecUtf8::from_env("SECSTR_TEST_FROM_ENV").unwrap();
+        assert_eq!(my_sec.unsecure(), "hunter2");
+        // the entry is gone afterwards
+        assert_eq!(std::env::var("SECSTR_TEST_FROM_ENV"), Err(std::env::VarError::NotPresent));
+        assert!(SecUtf8::from_env("SECSTR_TEST_NO_SUCH_VAR").is_err());
+    }
+
+    #[test]
+    fn test_utf8_ct_eq_hide_len() {
+        assert!(SecUtf8::from("hunter2").ct_eq_hide_len(&SecUtf8::from("hunter2")));
+        assert!(!SecUtf8::from("hunter2").ct_eq_hide_len(&SecUtf8::from("hunter3")));
+        // length mismatches in both directions, including a shared prefix
+        assert!(!SecUtf8::from("hunter2").ct_eq_hide_len(&SecUtf8::from("hunter")));
+        assert!(!SecUtf8::from("hunter").ct_eq_hide_len(&SecUtf8::from("hunter2")));
+        assert!(!SecUtf8::from("hunter2").ct_eq_hide_len(&SecUtf8::from("")));
+        assert!(!SecUtf8::from("").ct_eq_hide_len(&SecUtf8::from("hunter2")));
+        assert!(SecUtf8::from("").ct_eq_hide_len(&SecUtf8::from("")));
+    }
+
+    #[test]
+    fn test_utf8_read_line() {
+        let mut input = &b"hunter2\nrest"[..];
+        assert_eq!(SecUtf8::read_line(&mut input).unwrap(), SecUtf8::from("hunter2"));
+        // the reader is left just past the newline
+        assert_eq!(input, b"rest");
+        // CRLF framing, empty lines, EOF with and without trailing newline
+        let mut crlf = &b"hunter2\r\n"[..];
+        assert_eq!(SecUtf8::read_line(&mut crlf).unwrap(), SecUtf8::from("hunter2"));
+        let mut empty_line = &b"\nnext"[..];
+        assert_eq!(SecUtf8::read_line(&mut empty_line).unwrap(), SecUtf8::from(""));
+        let mut no_newline = &b"hunter2"[..];
+        assert_eq!(SecUtf8::read_line(&mut no_newline).unwrap(), SecUtf8::from("hunter2"));
+        // a `\r` at EOF is content, not CRLF framing
+        let mut cr_at_eof = &b"secret\r"[..];
+        assert_eq!(SecUtf8::read_line(&mut cr_at_eof).unwrap(), SecUtf8::from("secret\r"));
+        let mut eof = &b""[..];
+        assert_eq!(SecUtf8::read_line(&mut eof).unwrap(), SecUtf8::from(""));
+        // invalid UTF-8 fails loudly
+        let mut bad = &b"\xFF\xFE\n"[..];
+        let err = SecUtf8::read_line(&mut bad).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_utf8_trim() {
+        let mut my_sec = SecUtf8::from("  hunter2\n");
+        my_sec.trim_in_place();
+        assert_eq!(my_sec.unsecure(), "hunter2");
+        // the vacated tail must have been wiped
+        unsafe { my_sec.0.content.set_len(10) };
+        assert_eq!(my_sec.0.content[7..], [0, 0, 0]);
+        unsafe { my_sec.0.content.set_len(7) };
+
+        let mut all_ws = SecUtf8::from(" \t\n");
+        all_ws.trim_in_place();
+        assert_eq!(all_ws.unsecure(), "");
+
+        assert_eq!(SecUtf8::from_trimmed("\thunter2 \n"), SecUtf8::from("hunter2"));
+        // non-ASCII whitespace is left alone
+        assert_eq!(SecUtf8::from_trimmed("\u{a0}x"), SecUtf8::from("\u{a0}x"));
+    }
+
+    #[test]
+    fn test_utf8_eq_secstr() {
+        assert_eq!(SecUtf8::from("hello"), SecStr::from("hello"));
+        assert_eq!(SecStr::from("hello"), SecUtf8::from("hello"));
+        assert_ne!(SecUtf8::from("hello"), SecStr::from("yolo!"));
+        assert_ne!(SecStr::from("yolo!"), SecUtf8::from("hello"));
+    }
+
+    #[test]
+    fn test_utf8_into_unsecure() {
+        let my_sec = SecUtf8::from("hello");
+        let ptr = my_sec.unsecure().as_ptr();
+        let back = my_sec.into_unsecure();
+        // not wiped, not double-dropped: the same buffer comes back intact
+        assert_eq!(back, "hello".to_string());
+        assert_eq!(back.as_ptr(), ptr);
+    }
+
+    #[cfg(feature = "deref")]
+    #[test]
+    fn test_utf8_str_views() {
+        fn takes_str(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+        let my_sec = SecUtf8::from("hello");
+        assert_eq!(takes_str(&my_sec), 5);
+    }
+
+    #[test]
+    fn test_utf8_hashmap() {
+        // content-keyed maps work with the full `SecUtf8` as the key;
+        // there is deliberately no `Borrow<str>` for `&str` lookups, which
+        // would need `Hash` to agree with `str`'s (it hashes like
+        // `SecStr`, see `test_utf8_hashing`)
+        let mut map = std::collections::HashMap::new();
+        map.insert(SecUtf8::from("hunter2"), 42);
+        assert_eq!(map.get(&SecUtf8::from("hunter2")), Some(&42));
+        assert_eq!(map.get(&SecUtf8::from("*******")), None);
+    }
+
+    #[test]
+    fn test_utf8_show() {
+        assert_eq!(format!("{:?}", SecUtf8::from("hello")), "***SECRET***");
+        assert_eq!(format!("{}", SecUtf8::from("hello")), "***SECRET***");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_utf8_serialise_deserialise() {
+        let my_sec = SecUtf8::from("hello");
+        let json = serde_json::to_string(&my_sec).unwrap();
+        assert_eq!(json, "\"hello\"");
+        let my_sec2: SecUtf8 = serde_json::from_str(&json).unwrap();
+        assert_eq!(my_sec, my_sec2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_utf8_deserialise_bytes() {
+        // CBOR can carry a string field as a byte string
+        let buf = serde_cbor::to_vec(&serde_cbor::Value::Bytes(b"hello".to_vec())).unwrap();
+        let my_sec: SecUtf8 = serde_cbor::from_slice(&buf).unwrap();
+        assert_eq!(my_sec, SecUtf8::from("hello"));
+        let buf = serde_cbor::to_vec(&serde_cbor::Value::Bytes(vec![0xff, 0xfe])).unwrap();
+        assert!(serde_cbor::from_slice::<SecUtf8>(&buf).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_opt() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Config {
+            #[serde(default, with = "crate::serde_opt")]
+            password: Option<SecUtf8>,
+        }
+        let present: Config = serde_json::from_str(r#"{"password": "hunter2"}"#).unwrap();
+        assert_eq!(present.password, Some(SecUtf8::from("hunter2")));
+        let empty: Config = serde_json::from_str(r#"{"password": ""}"#).unwrap();
+        assert_eq!(empty.password, None);
+        let null: Config = serde_json::from_str(r#"{"password": null}"#).unwrap();
+        assert_eq!(null.password, None);
+        let absent: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(absent.password, None);
+        assert_eq!(serde_json::to_string(&present).unwrap(), r#"{"password":"hunter2"}"#);
+        assert_eq!(serde_json::to_string(&absent).unwrap(), r#"{"password":""}"#);
+    }
+
+    #[test]
+    fn test_secbox_basic() {
+        let my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        assert_eq!(my_sec, SecBox::new(Box::new([1u8, 2, 3])));
+        assert_ne!(my_sec, SecBox::new(Box::new([1u8, 2, 4])));
+        assert_eq!(my_sec.unsecure(), &[1u8, 2, 3]);
+        assert_eq!(my_sec[1], 2);
+    }
+
+    #[test]
+    fn test_secbox_ct_eq_value() {
+        let my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        assert!(my_sec.ct_eq_value(&[1u8, 2, 3]));
+        assert!(!my_sec.ct_eq_value(&[1u8, 2, 4]));
+        let my_sec = SecBox::new(Box::new(0xDEADu64));
+        assert!(my_sec.ct_eq_value(&0xDEAD));
+        assert!(!my_sec.ct_eq_value(&0xBEEF));
+    }
+
+    #[test]
+    fn test_secbox_wipe() {
+        let mut my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        my_sec.wipe();
+        assert_eq!(my_sec.unsecure(), &[0u8, 0, 0]);
+        let mut my_sec = SecBox::new(Box::new(42u64));
+        my_sec.wipe();
+        assert_eq!(*my_sec.unsecure(), 0);
+        // arrays of every length are covered, same as `NoPaddingBytes`
+        let mut my_sec = SecBox::new(Box::new([7u8; 48]));
+        my_sec.wipe();
+        assert_eq!(my_sec.unsecure(), &[0u8; 48]);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_secbox_safe_zero_out() {
+        let mut my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        my_sec.zero_out();
+        assert_eq!(my_sec.unsecure(), &[0u8, 0, 0]);
+    }
+
+    #[test]
+    fn test_secbox_as_slice() {
+        fn takes_bytes(bytes: &[u8]) -> usize {
+            bytes.len()
+        }
+        let mut my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        assert_eq!(takes_bytes(my_sec.as_slice()), 3);
+        my_sec.as_mut_slice()[0] = 9;
+        assert_eq!(my_sec.unsecure(), &[9u8, 2, 3]);
+    }
+
+    #[test]
+    fn test_secbox_from_array() {
+        let my_sec = SecBox::from([1u8, 2, 3, 4]);
+        assert_eq!(my_sec.unsecure(), &[1u8, 2, 3, 4]);
+        assert_eq!(my_sec, SecBox::new(Box::new([1u8, 2, 3, 4])));
+        // not limited to bytes
+        let words = SecBox::from([1u64, 2]);
+        assert_eq!(words.unsecure(), &[1u64, 2]);
+    }
+
+    #[test]
+    fn test_secbox_eq_secstr() {
+        let boxed = SecBox::from(*b"0123456789abcdef0123456789abcdef");
+        assert_eq!(boxed, SecStr::from("0123456789abcdef0123456789abcdef"));
+        assert_eq!(SecStr::from("0123456789abcdef0123456789abcdef"), boxed);
+        assert_ne!(boxed, SecStr::from("0123456789abcdef0123456789abcdeX"));
+        assert_ne!(boxed, SecStr::from("0123"));
+        assert_ne!(SecStr::from(""), boxed);
+    }
+
+    #[test]
+    fn test_secbox_into_unsecure() {
+        let my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        let ptr = my_sec.unsecure().as_ptr();
+        let back = my_sec.into_unsecure();
+        // not wiped, not double-dropped: the same allocation comes back intact
+        assert_eq!(*back, [1u8, 2, 3]);
+        assert_eq!(back.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_secbox_zst() {
+        // ZST handling in manual allocation is a classic footgun: run the
+        // whole lifecycle (new, clone, eq, explicit drop) over zero-sized
+        // contents and let the leak/UB checkers judge
+        let unit = SecBox::new(Box::new(()));
+        let unit2 = unit.clone();
+        assert_eq!(unit, unit2);
+        assert_eq!(format!("{:?}", unit), "***SECRET***");
+        drop(unit);
+        drop(unit2);
+        let empty = SecBox::new(Box::new([0u8; 0]));
+        assert_eq!(empty.as_slice(), &[] as &[u8]);
+        assert_eq!(empty, empty.clone());
+        assert_eq!(empty, SecBox::from([] as [u8; 0]));
+    }
+
+    #[test]
+    fn test_secbox_clone() {
+        let my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        assert_eq!(my_sec.clone(), my_sec);
+    }
+
+    #[test]
+    fn test_seclist() {
+        let mut list: SecList<SecStr> = SecList::new();
+        list.push(SecStr::from("hunter2"));
+        list.push(SecStr::from("correct horse"));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.get(0).unwrap().unsecure(), b"hunter2");
+        assert_eq!(format!("{:?}", list), "[2 secrets]");
+        // eager wipe reaches every element
+        list.zero_out();
+        assert!(list.iter().all(|s| s.unsecure().is_empty()));
+        list.clear();
+        assert!(list.is_empty());
+        // mixed nesting works too
+        let mut nested: SecList<SecList<SecStr>> = SecList::new();
+        nested.push(list);
+        nested.zero_out();
+    }
+
+    #[test]
+    fn test_boxed_slice_basic() {
+        let my_sec = SecBoxedSlice::new(vec![1u8, 2, 3].into_boxed_slice());
+        assert_eq!(my_sec, SecBoxedSlice::new(vec![1u8, 2, 3].into_boxed_slice()));
+        assert_ne!(my_sec, SecBoxedSlice::new(vec![1u8, 2].into_boxed_slice()));
+        assert_eq!(my_sec.unsecure(), &[1u8, 2, 3]);
+        assert_eq!(my_sec.clone(), my_sec);
+        assert_eq!(format!("{:?}", my_sec), "***SECRET***");
+    }
+
+    #[test]
+    fn test_boxed_slice_round_trip() {
+        // tight buffer: the same allocation all the way around
+        let mut my_sec = SecStr::from("hunter2");
+        my_sec.shrink_to_fit();
+        let ptr = my_sec.as_ptr();
+        let boxed = my_sec.into_boxed_slice();
+        assert_eq!(boxed.unsecure(), b"hunter2");
+        assert_eq!(boxed.unsecure().as_ptr(), ptr);
+        let back = boxed.into_sec_vec();
+        assert_eq!(back.unsecure(), b"hunter2");
+        assert_eq!(back.as_ptr(), ptr);
+        // spare capacity: contents move, result is tight
+        let mut roomy = SecStr::from("hunter2");
+        roomy.reserve_exact(1024);
+        let boxed = roomy.into_boxed_slice();
+        assert_eq!(boxed.unsecure(), b"hunter2");
+    }
+
+    #[test]
+    fn test_secbox_show() {
+        let my_sec = SecBox::new(Box::new([1u8, 2, 3]));
+        assert_eq!(format!("{:?}", my_sec), "***SECRET***");
+        assert_eq!(format!("{}", my_sec), "***SECRET***");
+    }
+}
@@ -0,0 +1,244 @@
+//! Handling of raw memory: constant time comparison and zeroing that the
+//! optimizer cannot elide, backed by libsodium when the `libsodium-sys`
+//! feature is enabled.
+//!
+//! Backend selection: `backend-sodium` (implies `libsodium-sys`) and
+//! `backend-pure` pick the implementation explicitly; enabling both is a
+//! compile error. With neither backend feature set, the historical
+//! implicit rule applies — libsodium if `libsodium-sys` is in the build,
+//! the pure-Rust loops otherwise. `backend-pure` wins over a
+//! `libsodium-sys` that some other dependency happened to enable, which
+//! is the auditability case it exists for.
+
+use std::mem::size_of;
+use std::ptr;
+
+/// Compare the `count` elements of `T` starting at `us` and `them` in
+/// constant time: the runtime depends on `count` (and `size_of::<T>()`),
+/// but not on the contents of either buffer.
+///
+/// Preconditions:
+///
+/// - `us` points to at least `count` consecutive, initialized values of `T`
+/// - `them` points to at least `count` consecutive, initialized values of `T`
+/// - `T` has no padding bytes (uninitialized bytes in the representation
+///   would make the byte-wise comparison read uninitialized memory)
+#[cfg(any(not(feature = "libsodium-sys"), feature = "backend-pure"))]
+#[inline(never)]
+pub(crate) unsafe fn cmp<T: Sized + Copy>(us: *const T, them: *const T, count: usize) -> bool {
+    const WORD: usize = size_of::<usize>();
+    let len = count * size_of::<T>();
+    let us = us as *const u8;
+    let them = them as *const u8;
+    // bulk of the buffer: word-at-a-time, with unaligned loads so neither
+    // pointer's alignment matters; `black_box` keeps the optimizer from
+    // turning the accumulation into an early-exit memcmp
+    let words = len / WORD;
+    let mut acc: usize = 0;
+    for i in 0..words {
+        let a = ptr::read_unaligned(us.add(i * WORD) as *const usize);
+        let b = ptr::read_unaligned(them.add(i * WORD) as *const usize);
+        acc |= std::hint::black_box(a ^ b);
+    }
+    // tail: the volatile byte loop
+    let mut result: u8 = 0;
+    for i in (words * WORD)..len {
+        result |= ptr::read_volatile(us.add(i)) ^ ptr::read_volatile(them.add(i));
+    }
+    (acc | result as usize) == 0
+}
+
+/// Compare the `count` elements of `T` starting at `us` and `them` in
+/// constant time, using libsodium's `sodium_memcmp`.
+///
+/// Preconditions: same as the pure-Rust version.
+#[cfg(all(feature = "libsodium-sys", not(feature = "backend-pure")))]
+pub(crate) unsafe fn cmp<T: Sized + Copy>(us: *const T, them: *const T, count: usize) -> bool {
+    libsodium_sys::sodium_memcmp(
+        us as *const libc::c_void,
+        them as *const libc::c_void,
+        count * size_of::<T>(),
+    ) == 0
+}
+
+/// Overwrite the `count` elements of `T` starting at `ptr` with zero bytes,
+/// in a way the optimizer will not remove even if the memory is never read
+/// again afterwards: byte-wide volatile stores for the unaligned head and
+/// tail, word-wide volatile stores for the aligned middle. Every store is
+/// still volatile, so the not-optimized-away guarantee is unchanged — the
+/// wider stores just make wiping large secrets cheap.
+///
+/// Preconditions:
+///
+/// - `ptr` points to at least `count` consecutive values of `T`, all of
+///   which may be overwritten (the word stores stay within the same
+///   region; alignment is handled here, not assumed)
+#[cfg(any(not(feature = "libsodium-sys"), feature = "backend-pure"))]
+#[inline(never)]
+pub(crate) unsafe fn zero<T: Sized + Copy>(ptr: *mut T, count: usize) {
+    const WORD: usize = size_of::<usize>();
+    let len = count * size_of::<T>();
+    let ptr = ptr as *mut u8;
+    let mut i = 0;
+    // head: up to the first word boundary (align_offset can exceed a
+    // short buffer's length)
+    let head = std::cmp::min(ptr.align_offset(WORD), len);
+    while i < head {
+        ptr::write_volatile(ptr.add(i), 0);
+        i += 1;
+    }
+    // aligned middle, a word at a time
+    while i + WORD <= len {
+        ptr::write_volatile(ptr.add(i) as *mut usize, 0);
+        i += WORD;
+    }
+    // tail
+    while i < len {
+        ptr::write_volatile(ptr.add(i), 0);
+        i += 1;
+    }
+}
+
+/// Overwrite the `count` elements of `T` starting at `ptr` with zero bytes,
+/// using libsodium's `sodium_memzero`.
+///
+/// Preconditions: same as the pure-Rust version.
+#[cfg(all(feature = "libsodium-sys", not(feature = "backend-pure")))]
+pub(crate) unsafe fn zero<T: Sized + Copy>(ptr: *mut T, count: usize) {
+    libsodium_sys::sodium_memzero(ptr as *mut libc::c_void, count * size_of::<T>());
+}
+
+/// Copy the `count` elements of `T` starting at `src` over the ones
+/// starting at `dst` if `cond` is `1`; leave `dst` unchanged if `cond` is
+/// `0`. Every byte of both buffers is read and every byte of `dst` is
+/// written back either way, so which of the two happened is not observable
+/// through timing.
+///
+/// Preconditions:
+///
+/// - `dst` and `src` point to at least `count` consecutive, initialized
+///   values of `T` and do not overlap
+/// - `cond` is `0` or `1`
+/// - `T` has no padding bytes
+#[inline(never)]
+pub(crate) unsafe fn copy_if<T: Sized + Copy>(dst: *mut T, src: *const T, count: usize, cond: u8) {
+    let len = count * size_of::<T>();
+    let dst = dst as *mut u8;
+    let src = src as *const u8;
+    // 0x00 when `cond` is 0, 0xFF when `cond` is 1
+    let mask = cond.wrapping_neg();
+    for i in 0..len {
+        let d = ptr::read_volatile(dst.add(i));
+        let s = ptr::read_volatile(src.add(i));
+        ptr::write_volatile(dst.add(i), d ^ (mask & (d ^ s)));
+    }
+}
+
+/// Accumulate the byte-wise difference of the `count` elements of `T`
+/// starting at `us` and `them`: returns `0` if the buffers are equal and a
+/// non-zero byte otherwise, without ever branching on the contents. This is
+/// the same accumulation `cmp` performs, exposed without the final
+/// comparison so callers can keep working branchlessly on the result.
+///
+/// Preconditions: same as `cmp`.
+#[cfg(feature = "subtle")]
+#[inline(never)]
+pub(crate) unsafe fn diff<T: Sized + Copy>(us: *const T, them: *const T, count: usize) -> u8 {
+    let len = count * size_of::<T>();
+    let us = us as *const u8;
+    let them = them as *const u8;
+    let mut result: u8 = 0;
+    for i in 0..len {
+        result |= ptr::read_volatile(us.add(i)) ^ ptr::read_volatile(them.add(i));
+    }
+    result
+}
+
+/// Debug-build check (behind the `verify-zero` feature) that the `count`
+/// elements of `T` starting at `ptr` really are all-zero bytes, catching
+/// optimizer regressions that would elide the wipe as a dead store. A
+/// no-op in release builds.
+///
+/// Preconditions: `ptr` points to at least `count` consecutive values of
+/// `T` whose bytes are initialized.
+#[cfg(feature = "verify-zero")]
+pub(crate) unsafe fn verify_zeroed<T: Sized + Copy>(ptr: *const T, count: usize) {
+    debug_assert!(
+        std::slice::from_raw_parts(ptr as *const u8, count * size_of::<T>())
+            .iter()
+            .all(|b| *b == 0),
+        "a secured buffer was not zeroed before being freed"
+    );
+}
+
+/// First pass of the wipe when the `random-wipe` feature is enabled:
+/// overwrite the `count` elements of `T` starting at `ptr` with bytes from
+/// the OS CSPRNG. The write happens through an opaque syscall, so the
+/// optimizer cannot elide it; the usual volatile `zero` pass still runs
+/// afterwards, keeping the end state all-zero.
+///
+/// Preconditions: same as `zero`.
+#[cfg(feature = "random-wipe")]
+pub(crate) unsafe fn random_overwrite<T: Sized + Copy>(ptr: *mut T, count: usize) {
+    let bytes = std::slice::from_raw_parts_mut(ptr as *mut u8, count * size_of::<T>());
+    // on failure, fall through to the zero pass — the single-pass wipe is
+    // still performed
+    let _ = getrandom::getrandom(bytes);
+}
+
+#[cfg(all(test, any(not(feature = "libsodium-sys"), feature = "backend-pure")))]
+mod tests {
+    use super::*;
+
+    fn bytewise(a: &[u8], b: &[u8]) -> bool {
+        let mut result: u8 = 0;
+        for i in 0..a.len() {
+            result |= a[i] ^ b[i];
+        }
+        result == 0
+    }
+
+    #[test]
+    fn test_zero_every_byte() {
+        // large enough to exercise the word-wide middle, with offsets to
+        // hit every head/tail alignment combination
+        let mut base = vec![0xAAu8; 1 << 20];
+        for offset in 0..8 {
+            for len in [0, 1, 7, 8, 9, 63, (1 << 20) - 16] {
+                base.iter_mut().for_each(|b| *b = 0xAA);
+                let region = &mut base[offset..offset + len];
+                unsafe { zero(region.as_mut_ptr(), len) };
+                assert!(region.iter().all(|b| *b == 0));
+                // neighbours untouched
+                if offset > 0 {
+                    assert_eq!(base[offset - 1], 0xAA);
+                }
+                assert_eq!(base[offset + len], 0xAA);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmp_agrees_with_bytewise() {
+        // aligned and unaligned offsets, equal and unequal, across word
+        // boundaries and odd tails
+        let base: Vec<u8> = (0..255u8).cycle().take(300).collect();
+        for offset in 0..8 {
+            for len in [0, 1, 7, 8, 9, 63, 64, 65, 250] {
+                let a = &base[offset..offset + len];
+                let mut b = a.to_vec();
+                assert_eq!(unsafe { cmp(a.as_ptr(), b.as_ptr(), len) }, bytewise(a, &b));
+                assert!(unsafe { cmp(a.as_ptr(), b.as_ptr(), len) });
+                if len > 0 {
+                    // flip a byte in the word region and in the tail
+                    for flip in [0, len / 2, len - 1] {
+                        b[flip] ^= 0x80;
+                        assert_eq!(unsafe { cmp(a.as_ptr(), b.as_ptr(), len) }, bytewise(a, &b));
+                        assert!(!unsafe { cmp(a.as_ptr(), b.as_ptr(), len) });
+                        b[flip] ^= 0x80;
+                    }
+                }
+            }
+        }
+    }
+}
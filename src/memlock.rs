@@ -0,0 +1,262 @@
+//! Thin wrappers around `mlock`/`munlock` that also ask the kernel to keep
+//! the memory out of core dumps where the platform supports that.
+//!
+//! The kernel locks and unlocks whole pages, regardless of the sub-page
+//! range it is handed. To keep every `mlock` matched by an identically
+//! sized `munlock` (so the process's locked-page accounting doesn't drift
+//! when allocations aren't page-aligned), both wrappers round the pointer
+//! down and the length up to page boundaries before making any calls.
+//!
+//! Consistent rounding is all this buys: `mlock` is not reference-counted,
+//! so when two small secrets share a page, dropping one unlocks the page
+//! under the survivor, which may then reach swap despite reporting
+//! `is_locked`. The allocator decides who shares pages; secrets that must
+//! stay locked for their whole lifetime need page-granular allocations
+//! (see `SecGuardedBox` behind the `guard-pages` feature).
+//!
+//! Page sharing also rules out `MADV_WIPEONFORK` on heap secrets: the hint
+//! applies to whole pages, so set around a malloc'd buffer it would zero
+//! unrelated neighbours — other objects, allocator metadata — in any
+//! forked child, corrupting the heap of exactly the fork-without-exec
+//! programs it would protect. It is applied only to page-granular
+//! allocations the caller owns outright (`wipe_on_fork`, used by
+//! `SecGuardedBox`); heap secrets reach forked children as-is.
+//!
+//! The callers' side of that contract: every buffer is locked exactly once
+//! with its capacity at lock time, and unlocked once with that same
+//! capacity — on drop, or when a reallocation path (`grow_to`,
+//! `try_reserve`, `shrink_to_fit`, `into_unsecure`) retires it. The
+//! `test_vmlck_does_not_drift` test below watches the kernel's accounting
+//! for violations.
+//!
+//! On non-unix platforms these are no-ops.
+
+#[cfg(unix)]
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU8, Ordering};
+#[cfg(unix)]
+use std::sync::atomic::AtomicUsize;
+
+/// What to do when locking a buffer into physical memory fails (most
+/// commonly: `RLIMIT_MEMLOCK` exhausted). Set process-wide with
+/// [`set_lock_failure_policy`](fn.set_lock_failure_policy.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockFailurePolicy {
+    /// Carry on silently; the affected buffer may reach swap. The default,
+    /// and the crate's historical behavior. `is_locked` still reports the
+    /// failure per instance.
+    Ignore,
+    /// Emit a warning — through [`log`](https://docs.rs/log) when the
+    /// `log` feature is enabled, to stderr otherwise — and carry on.
+    Warn,
+    /// Panic. For deployments where swap protection is mandatory and an
+    /// unlocked secret is worse than a crash.
+    Panic,
+}
+
+static LOCK_FAILURE_POLICY: AtomicU8 = AtomicU8::new(LockFailurePolicy::Ignore as u8);
+
+/// Set the process-wide response to `mlock` failure. Takes effect for all
+/// subsequent allocations in all threads; typically called once at
+/// startup. The default is `Ignore`.
+pub fn set_lock_failure_policy(policy: LockFailurePolicy) {
+    LOCK_FAILURE_POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+fn on_lock_failure(byte_num: usize) {
+    let policy = match LOCK_FAILURE_POLICY.load(Ordering::Relaxed) {
+        x if x == LockFailurePolicy::Warn as u8 => LockFailurePolicy::Warn,
+        x if x == LockFailurePolicy::Panic as u8 => LockFailurePolicy::Panic,
+        _ => LockFailurePolicy::Ignore,
+    };
+    match policy {
+        LockFailurePolicy::Ignore => {}
+        LockFailurePolicy::Warn => {
+            #[cfg(feature = "log")]
+            log::warn!("secstr: failed to mlock a {} byte secret; it may reach swap", byte_num);
+            #[cfg(not(feature = "log"))]
+            eprintln!("secstr: failed to mlock a {} byte secret; it may reach swap", byte_num);
+        }
+        LockFailurePolicy::Panic => {
+            panic!("secstr: failed to mlock a {} byte secret", byte_num)
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn page_size() -> usize {
+    static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+    let mut size = PAGE_SIZE.load(Ordering::Relaxed);
+    if size == 0 {
+        size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        PAGE_SIZE.store(size, Ordering::Relaxed);
+    }
+    size
+}
+
+/// Round `cont..cont + byte_num` out to the containing range of whole pages.
+#[cfg(unix)]
+fn page_range<T: Sized>(cont: *const T, byte_num: usize) -> (*mut libc::c_void, usize) {
+    let page = page_size();
+    let start = (cont as usize) & !(page - 1);
+    let end = (cont as usize)
+        .saturating_add(byte_num)
+        .saturating_add(page - 1)
+        & !(page - 1);
+    (start as *mut libc::c_void, end - start)
+}
+
+/// Lock the given range; the returned flag reports whether the `mlock`
+/// call itself succeeded (the `madvise` hints are best-effort either way).
+/// Locking zero bytes is a no-op and counts as success.
+#[cfg(unix)]
+pub(crate) fn mlock<T: Sized>(cont: *const T, count: usize) -> bool {
+    let byte_num = count * size_of::<T>();
+    if byte_num == 0 {
+        return true;
+    }
+    let (ptr, len) = page_range(cont, byte_num);
+    unsafe {
+        let locked = libc::mlock(ptr, len) == 0;
+        if !locked {
+            on_lock_failure(byte_num);
+        }
+        #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+        libc::madvise(ptr, len, libc::MADV_NOCORE);
+        #[cfg(target_os = "linux")]
+        libc::madvise(ptr, len, libc::MADV_DONTDUMP);
+        // no MADV_WIPEONFORK here: the rounded range may cover neighbours
+        // on shared pages, and wiping those in a forked child corrupts the
+        // heap — see the module docs and `wipe_on_fork`
+        locked
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn munlock<T: Sized>(cont: *const T, count: usize) {
+    let byte_num = count * size_of::<T>();
+    if byte_num == 0 {
+        return;
+    }
+    let (ptr, len) = page_range(cont, byte_num);
+    unsafe {
+        libc::munlock(ptr, len);
+        #[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+        libc::madvise(ptr, len, libc::MADV_CORE);
+        #[cfg(target_os = "linux")]
+        libc::madvise(ptr, len, libc::MADV_DODUMP);
+    }
+}
+
+/// Ask the kernel to zero the given range in any forked child (Linux
+/// 4.14+; silently ignored elsewhere, like every other hint here), so the
+/// secret doesn't leak into fork-without-exec workers. The hint applies to
+/// whole pages, so this is only sound for page-granular allocations the
+/// caller owns outright — never for malloc'd buffers, per the module docs.
+/// Cleared implicitly when the mapping is unmapped.
+#[cfg(unix)]
+#[cfg_attr(not(feature = "guard-pages"), allow(dead_code))]
+pub(crate) fn wipe_on_fork<T: Sized>(cont: *const T, count: usize) {
+    let byte_num = count * size_of::<T>();
+    if byte_num == 0 {
+        return;
+    }
+    let (ptr, len) = page_range(cont, byte_num);
+    #[cfg(target_os = "linux")]
+    unsafe {
+        libc::madvise(ptr, len, libc::MADV_WIPEONFORK);
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = (ptr, len);
+}
+
+#[cfg(not(unix))]
+pub(crate) fn mlock<T: Sized>(_cont: *const T, count: usize) -> bool {
+    // locking zero bytes is a no-op that counts as success on every
+    // platform, so `is_locked` on an empty buffer doesn't depend on the OS
+    count * std::mem::size_of::<T>() == 0
+}
+
+#[cfg(not(unix))]
+pub(crate) fn munlock<T: Sized>(_cont: *const T, _count: usize) {}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_range_covers_input() {
+        let buf = [0u8; 3];
+        let (ptr, len) = page_range(buf.as_ptr(), buf.len());
+        let page = page_size();
+        assert_eq!(ptr as usize % page, 0);
+        assert_eq!(len % page, 0);
+        assert!((ptr as usize) <= buf.as_ptr() as usize);
+        assert!(ptr as usize + len >= buf.as_ptr() as usize + buf.len());
+    }
+
+    /// Every `mlock` must be paired with an equally-sized `munlock` across
+    /// construction, growth, cloning and drop — otherwise the process's
+    /// locked-page accounting drifts until `RLIMIT_MEMLOCK` kills further
+    /// locking. Churn many secrets through all the reallocation paths and
+    /// check `VmLck` in `/proc/self/status` ends up where it started
+    /// (modulo other tests running concurrently, hence the slack).
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_vmlck_does_not_drift() {
+        fn vmlck_kb() -> usize {
+            std::fs::read_to_string("/proc/self/status")
+                .unwrap()
+                .lines()
+                .find(|l| l.starts_with("VmLck:"))
+                .and_then(|l| l.split_whitespace().nth(1))
+                .and_then(|v| v.parse().ok())
+                .unwrap()
+        }
+        let before = vmlck_kb();
+        for _ in 0..50 {
+            let mut secrets: Vec<crate::SecStr> =
+                (0..32).map(|i| crate::SecStr::from(vec![i as u8; 100])).collect();
+            for secret in secrets.iter_mut() {
+                secret.reserve_exact(5000); // force a reallocation
+                secret.shrink_to_fit(); // and another
+            }
+            let _clones: Vec<crate::SecStr> = secrets.to_vec();
+        }
+        let after = vmlck_kb();
+        // a real leak over 1600 secrets × several pages would be megabytes
+        assert!(
+            after.saturating_sub(before) < 1024,
+            "locked-page accounting drifted: {} kB -> {} kB",
+            before,
+            after
+        );
+    }
+
+    #[test]
+    fn test_lock_failure_policy() {
+        // the policy only fires on an actual mlock failure, which can't be
+        // forced portably, so exercise the handler directly
+        set_lock_failure_policy(LockFailurePolicy::Warn);
+        on_lock_failure(32); // prints to stderr, must not panic
+        set_lock_failure_policy(LockFailurePolicy::Panic);
+        assert!(std::panic::catch_unwind(|| on_lock_failure(32)).is_err());
+        set_lock_failure_policy(LockFailurePolicy::Ignore);
+        on_lock_failure(32);
+    }
+
+    #[test]
+    fn test_small_allocations_share_pages() {
+        // many small secrets packed into nearby allocations: the rounded
+        // lock/unlock calls must stay well-formed when several buffers sit
+        // on one page. This only exercises that the calls don't fault or
+        // corrupt anything — it cannot observe the residual hazard from
+        // the module docs (a drop munlocking a live neighbour's shared
+        // page), since munlock never affects readability.
+        let secrets: Vec<crate::SecStr> = (0..64).map(|i| crate::SecStr::from(vec![i as u8; 24])).collect();
+        for secret in &secrets {
+            assert_eq!(secret.unsecure().len(), 24);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Pooled secret buffers: pre-locked, pre-zeroed allocations of one fixed
+//! size that get reused instead of paying an alloc + `mlock` (and later
+//! `munlock` + free) cycle per secret. For high-throughput services
+//! churning through many same-sized secrets (session keys, nonces), where
+//! those syscalls dominate the cost of each secret; a buffer is zeroed
+//! when it is returned to the pool, so reuse never sees a previous
+//! tenant's bytes.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::{mem, memlock};
+
+/// A bounded pool of locked, zeroed byte buffers of one fixed size.
+/// [`take`](Self::take) hands out a [`PooledSec`] guard backed by a pooled
+/// buffer (or a freshly locked one when the pool is empty); dropping the
+/// guard zeroes the buffer and returns it to the pool rather than
+/// unlocking and freeing it. At most `max_pooled` buffers are retained —
+/// beyond that, a retiring buffer is zeroed, unlocked and freed like a
+/// plain `SecStr`'s. Buffers still held when the pool itself drops are
+/// unlocked and freed then (already zeroed on their way in).
+pub struct SecPool {
+    buf_size: usize,
+    max_pooled: usize,
+    free: Mutex<Vec<Vec<u8>>>,
+}
+
+impl SecPool {
+    /// A pool handing out `buf_size`-byte buffers, keeping at most
+    /// `max_pooled` of them around between uses. No buffers are allocated
+    /// up front; the pool fills as guards retire.
+    pub fn new(buf_size: usize, max_pooled: usize) -> SecPool {
+        SecPool {
+            buf_size,
+            max_pooled,
+            free: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The fixed size, in bytes, of every buffer this pool hands out.
+    pub fn buf_size(&self) -> usize {
+        self.buf_size
+    }
+
+    /// How many idle buffers the pool currently holds.
+    pub fn pooled(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+
+    /// Take a zeroed, locked `buf_size`-byte buffer out of the pool,
+    /// reusing an idle one when available and allocating (and locking) a
+    /// fresh one otherwise. The caller writes the secret in through
+    /// [`PooledSec::unsecure_mut`]; dropping the guard zeroes the buffer
+    /// and hands it back.
+    pub fn take(&self) -> PooledSec<'_> {
+        let content = self.free.lock().unwrap().pop().unwrap_or_else(|| {
+            let mut buf: Vec<u8> = Vec::with_capacity(self.buf_size);
+            // lock before the (zero) fill, like every other constructor
+            memlock::mlock(buf.as_ptr(), buf.capacity());
+            buf.resize(self.buf_size, 0);
+            buf
+        });
+        PooledSec { pool: self, content }
+    }
+
+    /// Zero, unlock and free a buffer that is not going back into the
+    /// pool — the same retirement every `SecStr` buffer gets.
+    fn retire(mut buf: Vec<u8>) {
+        let cap = buf.capacity();
+        unsafe {
+            buf.set_len(0);
+            mem::zero(buf.as_mut_ptr(), cap);
+            #[cfg(feature = "verify-zero")]
+            mem::verify_zeroed(buf.as_ptr(), cap);
+        }
+        memlock::munlock(buf.as_ptr(), cap);
+    }
+}
+
+impl fmt::Debug for SecPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecPool({} byte buffers, {} pooled)", self.buf_size, self.pooled())
+    }
+}
+
+impl Drop for SecPool {
+    fn drop(&mut self) {
+        for buf in self.free.get_mut().unwrap().drain(..) {
+            SecPool::retire(buf);
+        }
+    }
+}
+
+/// A `buf_size`-byte secret backed by a pooled buffer, handed out by
+/// [`SecPool::take`]: zeroed on arrival, zeroed again on drop before the
+/// buffer returns to the pool. Compares in constant time and formats as
+/// `***SECRET***` like the owning secret types.
+pub struct PooledSec<'pool> {
+    pool: &'pool SecPool,
+    content: Vec<u8>,
+}
+
+impl PooledSec<'_> {
+    /// Borrow the contents of the buffer.
+    #[must_use]
+    pub fn unsecure(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Mutably borrow the contents of the buffer.
+    #[must_use]
+    pub fn unsecure_mut(&mut self) -> &mut [u8] {
+        &mut self.content
+    }
+
+    /// The buffer length — always the pool's `buf_size`.
+    pub fn len(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Whether the buffer is zero-length (only for a zero-size pool).
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+}
+
+// Comparisons
+impl PartialEq for PooledSec<'_> {
+    fn eq(&self, other: &PooledSec<'_>) -> bool {
+        crate::constant_time_eq(&self.content, &other.content)
+    }
+}
+
+impl Eq for PooledSec<'_> {}
+
+// Make sure sensitive information is not logged accidentally
+impl fmt::Debug for PooledSec<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+impl fmt::Display for PooledSec<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***SECRET***")
+    }
+}
+
+// Delete sensitive information from memory — and recycle the buffer
+impl Drop for PooledSec<'_> {
+    fn drop(&mut self) {
+        let cap = self.content.capacity();
+        // SAFETY: the buffer holds `cap` reachable bytes (len never
+        // shrinks below `buf_size`), and zeroed bytes are valid `u8`s.
+        unsafe { mem::zero(self.content.as_mut_ptr(), cap) };
+        let buf = std::mem::take(&mut self.content);
+        let mut free = self.pool.free.lock().unwrap();
+        if free.len() < self.pool.max_pooled {
+            free.push(buf);
+        } else {
+            drop(free);
+            SecPool::retire(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_basic() {
+        let pool = SecPool::new(32, 4);
+        let mut secret = pool.take();
+        assert_eq!(secret.len(), 32);
+        assert!(secret.unsecure().iter().all(|b| *b == 0));
+        secret.unsecure_mut().copy_from_slice(&[0xAB; 32]);
+        assert_eq!(secret, {
+            let mut other = pool.take();
+            other.unsecure_mut().copy_from_slice(&[0xAB; 32]);
+            other
+        });
+        assert_eq!(format!("{:?}", secret), "***SECRET***");
+    }
+
+    #[test]
+    fn test_pool_reuses_and_zeroes() {
+        let pool = SecPool::new(16, 4);
+        let mut secret = pool.take();
+        secret.unsecure_mut().copy_from_slice(&[0xCD; 16]);
+        let ptr = secret.unsecure().as_ptr();
+        drop(secret);
+        assert_eq!(pool.pooled(), 1);
+        // the same buffer comes back, wiped of the previous tenant
+        let reused = pool.take();
+        assert_eq!(reused.unsecure().as_ptr(), ptr);
+        assert!(reused.unsecure().iter().all(|b| *b == 0));
+        assert_eq!(pool.pooled(), 0);
+    }
+
+    #[test]
+    fn test_pool_bound() {
+        let pool = SecPool::new(8, 2);
+        let guards: Vec<PooledSec<'_>> = (0..5).map(|_| pool.take()).collect();
+        drop(guards);
+        // only `max_pooled` buffers are retained; the rest were freed
+        assert_eq!(pool.pooled(), 2);
+    }
+}
@@ -0,0 +1,44 @@
+//! A secure-heap allocator backed by libsodium's `sodium_malloc`/
+//! `sodium_free`, behind the nightly-only `allocator-api` feature (which
+//! implies `libsodium-sys`).
+//!
+//! `sodium_malloc` places each allocation on its own guard-paged,
+//! `mlock`ed, canary-protected pages and `sodium_free` zeroes it, so a
+//! `Vec<u8, SodiumAllocator>` gets the secure-heap treatment for its
+//! entire lifetime — including the growth reallocations that happen before
+//! a plain `Vec` is ever handed to `SecVec::new`:
+//!
+//! ```ignore
+//! #![feature(allocator_api)]
+//! let mut buf: Vec<u8, SodiumAllocator> = Vec::new_in(SodiumAllocator);
+//! buf.extend_from_slice(b"secret material");
+//! ```
+//!
+//! Parameterizing `SecVec` itself over an allocator would require every
+//! impl in the crate to exist in stable and allocator-generic variants, so
+//! for now the allocator is exposed on its own; `libsodium-sys` users
+//! should call `sodium_init` once at startup before allocating.
+
+use std::alloc::{AllocError, Allocator, Layout};
+use std::ptr::NonNull;
+
+pub struct SodiumAllocator;
+
+unsafe impl Allocator for SodiumAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // sodium_malloc aligns for any fundamental type, but no further
+        if layout.align() > std::mem::align_of::<libc::max_align_t>() {
+            return Err(AllocError);
+        }
+        // SAFETY: any size is acceptable to sodium_malloc; failure is NULL.
+        let ptr = unsafe { libsodium_sys::sodium_malloc(layout.size()) };
+        NonNull::new(ptr as *mut u8)
+            .map(|p| NonNull::slice_from_raw_parts(p, layout.size()))
+            .ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, _layout: Layout) {
+        // zeroes the allocation before unmapping it
+        libsodium_sys::sodium_free(ptr.as_ptr() as *mut libc::c_void);
+    }
+}